@@ -1,3 +0,0 @@
-pub mod fatty_acid;
-pub mod mature_milk;
-pub mod polars;
@@ -0,0 +1,156 @@
+use crate::fatty_acid::FattyAcid;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+impl FattyAcid {
+    /// Chain length class
+    pub fn chain_length_class(&self, boundaries: &Boundaries) -> ChainLength {
+        boundaries.chain_length(self.carbons)
+    }
+
+    /// Parity of the number of carbons
+    pub fn parity(&self) -> Parity {
+        Parity::new(self.carbons)
+    }
+}
+
+/// Chain length class
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum ChainLength {
+    /// SCFA (Short chain fatty acid)
+    Short,
+    /// MCFA (Medium chain fatty acid)
+    Medium,
+    /// LCFA (Long chain fatty acid)
+    Long,
+    /// VLCFA (Very long chain fatty acid)
+    VeryLong,
+}
+
+impl fmt::Display for ChainLength {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Short => "SCFA",
+            Self::Medium => "MCFA",
+            Self::Long => "LCFA",
+            Self::VeryLong => "VLCFA",
+        })
+    }
+}
+
+/// Chain length class boundaries
+///
+/// The least number of carbons of each class. The default is SCFA < C6,
+/// MCFA C6-C12, LCFA C13-C21 and VLCFA ≥ C22. Other conventions put the
+/// boundaries at C8/C14 or C6/C12/C20, for example.
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub struct Boundaries {
+    pub medium: u8,
+    pub long: u8,
+    pub very_long: u8,
+}
+
+impl Boundaries {
+    pub fn chain_length(&self, carbons: u8) -> ChainLength {
+        if carbons < self.medium {
+            ChainLength::Short
+        } else if carbons < self.long {
+            ChainLength::Medium
+        } else if carbons < self.very_long {
+            ChainLength::Long
+        } else {
+            ChainLength::VeryLong
+        }
+    }
+
+    /// Carbons range of the class, the upper bound is exclusive.
+    pub fn range(&self, chain_length: ChainLength) -> (u8, Option<u8>) {
+        match chain_length {
+            ChainLength::Short => (0, Some(self.medium)),
+            ChainLength::Medium => (self.medium, Some(self.long)),
+            ChainLength::Long => (self.long, Some(self.very_long)),
+            ChainLength::VeryLong => (self.very_long, None),
+        }
+    }
+}
+
+impl Default for Boundaries {
+    fn default() -> Self {
+        Self {
+            medium: 6,
+            long: 13,
+            very_long: 22,
+        }
+    }
+}
+
+/// Parity
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize)]
+pub enum Parity {
+    /// OCFA (Odd chain fatty acid)
+    Odd,
+    /// ECFA (Even chain fatty acid)
+    Even,
+}
+
+impl Parity {
+    pub fn new(carbons: u8) -> Self {
+        if carbons.is_multiple_of(2) {
+            Self::Even
+        } else {
+            Self::Odd
+        }
+    }
+}
+
+impl fmt::Display for Parity {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        f.write_str(match self {
+            Self::Odd => "OCFA",
+            Self::Even => "ECFA",
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn chain_length_class() {
+        let boundaries = Boundaries::default();
+        let classes = [4, 5, 6, 12, 13, 18, 21, 22, 26]
+            .map(|carbons| FattyAcid::new(carbons).chain_length_class(&boundaries));
+        assert_eq!(
+            classes,
+            [
+                ChainLength::Short,
+                ChainLength::Short,
+                ChainLength::Medium,
+                ChainLength::Medium,
+                ChainLength::Long,
+                ChainLength::Long,
+                ChainLength::Long,
+                ChainLength::VeryLong,
+                ChainLength::VeryLong,
+            ],
+        );
+        let boundaries = Boundaries {
+            medium: 8,
+            long: 14,
+            very_long: 20,
+        };
+        assert_eq!(
+            fatty_acid!(6).chain_length_class(&boundaries),
+            ChainLength::Short
+        );
+        assert_eq!(
+            fatty_acid!(20;5,8,11,14,17).chain_length_class(&boundaries),
+            ChainLength::VeryLong,
+        );
+        assert_eq!(fatty_acid!(17;9).parity(), Parity::Odd);
+        assert_eq!(fatty_acid!(18;9).parity(), Parity::Even);
+        assert_eq!(ChainLength::VeryLong.to_string(), "VLCFA");
+    }
+}
@@ -0,0 +1,378 @@
+use crate::fatty_acid::{
+    FattyAcid,
+    index::{Registry, Term},
+    sort,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    error,
+    fmt::{self, Formatter},
+    str::FromStr,
+};
+
+/// Fatty acid composition
+///
+/// Values by fatty acid, in insertion order, without polars. The `polars`
+/// feature adds `from_data_frame` and `to_data_frame` for the conversion.
+///
+/// Fatty acids are keyed with the unsaturated bonds in the order of a fatty
+/// acid series, so `18:2Δ12,9` and `18:2Δ9,12` are the same key.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "Values")]
+pub struct Composition {
+    pub unit: Unit,
+    values: Vec<(FattyAcid, f64)>,
+}
+
+/// Unchecked composition, deserialized through [`Composition::insert`]
+#[derive(Deserialize)]
+struct Values {
+    unit: Unit,
+    values: Vec<(FattyAcid, f64)>,
+}
+
+impl TryFrom<Values> for Composition {
+    type Error = Error;
+
+    fn try_from(value: Values) -> Result<Self, Self::Error> {
+        let mut composition = Self::new(value.unit);
+        for (fatty_acid, value) in value.values {
+            if composition.get(&fatty_acid).is_some() {
+                return Err(Error::Duplicate(fatty_acid));
+            }
+            composition.insert(fatty_acid, value);
+        }
+        Ok(composition)
+    }
+}
+
+impl Composition {
+    pub fn new(unit: Unit) -> Self {
+        Self {
+            unit,
+            values: Vec::new(),
+        }
+    }
+
+    pub fn from_ron(text: &str) -> ron::error::SpannedResult<Self> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(text)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default()
+                .extensions(ron::extensions::Extensions::IMPLICIT_SOME),
+        )
+    }
+
+    /// Value of the fatty acid
+    pub fn get(&self, fatty_acid: &FattyAcid) -> Option<f64> {
+        let fatty_acid = key(fatty_acid.clone());
+        self.values
+            .iter()
+            .find(|(other, _)| *other == fatty_acid)
+            .map(|&(_, value)| value)
+    }
+
+    /// Insert the value, returns the replaced one
+    pub fn insert(&mut self, fatty_acid: FattyAcid, value: f64) -> Option<f64> {
+        let fatty_acid = key(fatty_acid);
+        match self
+            .values
+            .iter_mut()
+            .find(|(other, _)| *other == fatty_acid)
+        {
+            Some((_, other)) => Some(std::mem::replace(other, value)),
+            None => {
+                self.values.push((fatty_acid, value));
+                None
+            }
+        }
+    }
+
+    /// Remove the fatty acid, returns its value
+    pub fn remove(&mut self, fatty_acid: &FattyAcid) -> Option<f64> {
+        let fatty_acid = key(fatty_acid.clone());
+        let index = self
+            .values
+            .iter()
+            .position(|(other, _)| *other == fatty_acid)?;
+        Some(self.values.remove(index).1)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&FattyAcid, f64)> {
+        self.values
+            .iter()
+            .map(|(fatty_acid, value)| (fatty_acid, *value))
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// ΣFA
+    pub fn total(&self) -> f64 {
+        self.values.iter().map(|(_, value)| value).sum()
+    }
+
+    /// Normalize to 100 %
+    ///
+    /// Normalized mg/g are mass %. A composition summing to 0 is left as is,
+    /// unit included.
+    pub fn normalize(&mut self) {
+        let total = self.total();
+        if total == 0.0 {
+            return;
+        }
+        for (_, value) in &mut self.values {
+            *value *= 100.0 / total;
+        }
+        if self.unit == Unit::MilligramsPerGram {
+            self.unit = Unit::Mass;
+        }
+    }
+
+    /// Normalized to 100 %, see [`normalize`](Self::normalize)
+    pub fn normalized(mut self) -> Self {
+        self.normalize();
+        self
+    }
+
+    /// Sum of the term, see [`Term::evaluate`]
+    pub fn sum(&self, term: &Term) -> f64 {
+        term.evaluate(self)
+    }
+
+    /// ΣSFA
+    pub fn sfa(&self) -> f64 {
+        self.sum(&Term::Sfa)
+    }
+
+    /// ΣMUFA
+    pub fn mufa(&self) -> f64 {
+        self.sum(&Term::Mufa)
+    }
+
+    /// ΣPUFA
+    pub fn pufa(&self) -> f64 {
+        self.sum(&Term::Pufa)
+    }
+
+    /// Σω-3
+    pub fn n3(&self) -> f64 {
+        self.sum(&Term::N(3))
+    }
+
+    /// Σω-6
+    pub fn n6(&self) -> f64 {
+        self.sum(&Term::N(6))
+    }
+
+    /// ΣTFA, including conjugated isomers if set
+    pub fn tfa(&self, conjugated: bool) -> f64 {
+        self.sum(&Term::Tfa(conjugated))
+    }
+
+    /// Values of the indices in the registry, e.g. the built-in nutritional
+    /// indices of [`Registry::default`]
+    pub fn indices<'a>(&self, registry: &'a Registry) -> Vec<(&'a str, f64)> {
+        registry
+            .indices
+            .iter()
+            .map(|index| (index.name.as_str(), index.formula.evaluate(self)))
+            .collect()
+    }
+}
+
+impl Extend<(FattyAcid, f64)> for Composition {
+    fn extend<T: IntoIterator<Item = (FattyAcid, f64)>>(&mut self, iter: T) {
+        for (fatty_acid, value) in iter {
+            self.insert(fatty_acid, value);
+        }
+    }
+}
+
+/// A [`Unit::Area`] composition
+impl FromIterator<(FattyAcid, f64)> for Composition {
+    fn from_iter<T: IntoIterator<Item = (FattyAcid, f64)>>(iter: T) -> Self {
+        let mut composition = Self::default();
+        composition.extend(iter);
+        composition
+    }
+}
+
+fn key(mut fatty_acid: FattyAcid) -> FattyAcid {
+    sort(&mut fatty_acid.unsaturated);
+    fatty_acid
+}
+
+/// Composition unit
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Unit {
+    /// Area %
+    #[default]
+    Area,
+    /// Mass %
+    Mass,
+    /// Mol %
+    Mole,
+    /// mg/g
+    MilligramsPerGram,
+}
+
+impl fmt::Display for Unit {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Area => f.write_str("area %"),
+            Self::Mass => f.write_str("mass %"),
+            Self::Mole => f.write_str("mol %"),
+            Self::MilligramsPerGram => f.write_str("mg/g"),
+        }
+    }
+}
+
+/// Parses the [`Display`](fmt::Display) label
+impl FromStr for Unit {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "area %" => Ok(Self::Area),
+            "mass %" => Ok(Self::Mass),
+            "mol %" => Ok(Self::Mole),
+            "mg/g" => Ok(Self::MilligramsPerGram),
+            _ => Err(Error::Unit(s.to_owned())),
+        }
+    }
+}
+
+/// Composition error
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// Duplicate fatty acid
+    Duplicate(FattyAcid),
+    /// Unknown unit label
+    Unit(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Duplicate(fatty_acid) => write!(f, "duplicate fatty acid {fatty_acid:?}"),
+            Self::Unit(unit) => write!(f, "unknown unit `{unit}`"),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{Unsaturated, fatty_acid};
+
+    fn composition() -> Composition {
+        Composition::from_iter([
+            (fatty_acid!(16), 20.0),
+            (fatty_acid!(18;9), 40.0),
+            (fatty_acid!(18;-9), 2.0),
+            (fatty_acid!(18;9,-11), 1.0),
+            (fatty_acid!(18;9,12), 25.0),
+            (fatty_acid!(18;9,12,15), 5.0),
+            (fatty_acid!(20;5,8,11,14,17), 7.0),
+        ])
+    }
+
+    #[test]
+    fn map() {
+        let mut composition = composition();
+        assert_eq!(composition.len(), 7);
+        assert_eq!(composition.get(&fatty_acid!(18;9)), Some(40.0));
+        assert_eq!(composition.insert(fatty_acid!(18;9), 30.0), Some(40.0));
+        assert_eq!(composition.insert(fatty_acid!(14), 10.0), None);
+        assert_eq!(composition.remove(&fatty_acid!(14)), Some(10.0));
+        assert_eq!(composition.len(), 7);
+        assert_eq!(composition.total(), 90.0);
+        // Canonical keys
+        assert_eq!(composition.get(&fatty_acid!(18;12,9)), Some(25.0));
+        assert_eq!(composition.insert(fatty_acid!(18;-11,9), 2.0), Some(1.0));
+        assert_eq!(composition.remove(&fatty_acid!(18;15,12,9)), Some(5.0));
+        assert_eq!(composition.len(), 6);
+    }
+
+    #[test]
+    fn sums() {
+        let composition = composition();
+        assert_eq!(composition.sfa(), 20.0);
+        assert_eq!(composition.mufa(), 42.0);
+        assert_eq!(composition.pufa(), 38.0);
+        assert_eq!(composition.n3(), 12.0);
+        // 18:2Δ9,11t is ω-7
+        assert_eq!(composition.n6(), 25.0);
+        assert_eq!(composition.tfa(false), 2.0);
+        assert_eq!(composition.tfa(true), 3.0);
+    }
+
+    #[test]
+    fn normalize() {
+        let mut composition = composition();
+        composition.unit = Unit::MilligramsPerGram;
+        composition.remove(&fatty_acid!(16));
+        let composition = composition.normalized();
+        assert_eq!(composition.unit, Unit::Mass);
+        assert!((composition.total() - 100.0).abs() < 1e-9);
+        assert!((composition.get(&fatty_acid!(18;9)).unwrap() - 50.0).abs() < 1e-9);
+        // A zero total changes neither the values nor the unit
+        let mut zero = Composition::new(Unit::MilligramsPerGram);
+        zero.insert(fatty_acid!(16), 0.0);
+        let zero = zero.normalized();
+        assert_eq!(zero.unit, Unit::MilligramsPerGram);
+        assert_eq!(zero.get(&fatty_acid!(16)), Some(0.0));
+    }
+
+    #[test]
+    fn unit() {
+        for unit in [Unit::Area, Unit::Mass, Unit::Mole, Unit::MilligramsPerGram] {
+            assert_eq!(unit.to_string().parse(), Ok(unit));
+        }
+        assert_eq!("%".parse::<Unit>(), Err(Error::Unit("%".to_owned())));
+    }
+
+    #[test]
+    fn ron() {
+        let mut composition = composition();
+        composition.unit = Unit::Mole;
+        let text = composition.to_ron().unwrap();
+        assert_eq!(Composition::from_ron(&text).unwrap(), composition);
+        // Unsorted bonds are canonical, duplicates are errors
+        let bond = |index| Unsaturated {
+            index: Some(index),
+            ..Default::default()
+        };
+        let composition = Composition::from_ron(
+            "(unit: Area, values: [((carbons: 18, unsaturated: [(index: 12), (index: 9)]), 25.0)])",
+        )
+        .unwrap();
+        let expected = FattyAcid {
+            carbons: 18,
+            unsaturated: vec![bond(9), bond(12)],
+        };
+        assert_eq!(composition.iter().next(), Some((&expected, 25.0)));
+        assert!(
+            Composition::from_ron(
+                "(unit: Area, values: [
+                    ((carbons: 18, unsaturated: [(index: 12), (index: 9)]), 25.0),
+                    ((carbons: 18, unsaturated: [(index: 9), (index: 12)]), 5.0),
+                ])",
+            )
+            .is_err()
+        );
+    }
+}
@@ -0,0 +1,107 @@
+use crate::{
+    r#const::relative_atomic_mass::{C, H, O},
+    fatty_acid::{FattyAcid, FattyAcidExt as _},
+};
+use serde::{Deserialize, Serialize};
+
+impl FattyAcid {
+    /// Mass of the derivative
+    pub fn mass(&self, derivative: Derivative) -> f64 {
+        self.carbons as f64 * C + self.hydrogens() as f64 * H + 2. * O + derivative.delta()
+    }
+
+    /// Effective carbons
+    ///
+    /// The carbons of the derivative responding in a flame ionization
+    /// detector, the carbonyl carbon gives no response (Ackman & Sipos, 1964).
+    /// Not to be confused with the equivalent carbon number
+    /// [`ecn`](super::Short::ecn), a retention index.
+    pub fn effective_carbons(&self, derivative: Derivative) -> u8 {
+        self.carbons.saturating_sub(1) + derivative.carbons()
+    }
+
+    /// Theoretical FID response factor relative to the `reference`
+    ///
+    /// `F = (M / EC) / (Mref / ECref)` (Craske & Bannon, 1987). Mass is
+    /// proportional to `F * area`.
+    pub fn response_factor(&self, derivative: Derivative, reference: &FattyAcid) -> f64 {
+        let factor = |fatty_acid: &FattyAcid| {
+            fatty_acid.mass(derivative) / fatty_acid.effective_carbons(derivative) as f64
+        };
+        factor(self) / factor(reference)
+    }
+}
+
+/// Derivative
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub enum Derivative {
+    /// FA (Free fatty acid)
+    #[default]
+    Acid,
+    /// FAME (Fatty acid methyl ester)
+    Methyl,
+    /// FAEE (Fatty acid ethyl ester)
+    Ethyl,
+    /// TAG (Triacylglycerol), the share of one acyl of a simple
+    /// triacylglycerol
+    Triacylglycerol,
+}
+
+impl Derivative {
+    /// The carbons added to the free fatty acid
+    pub fn carbons(&self) -> u8 {
+        match self {
+            Self::Acid => 0,
+            Self::Methyl => 1,
+            Self::Ethyl => 2,
+            Self::Triacylglycerol => 1,
+        }
+    }
+
+    /// The mass added to the free fatty acid
+    ///
+    /// `R-COOH` → `R-COOCH3` adds `CH2`, `R-COOC2H5` adds `C2H4`, and
+    /// `3 R-COOH + C3H8O3 - 3 H2O` adds `C3H2` per three acyls.
+    pub fn delta(&self) -> f64 {
+        match self {
+            Self::Acid => 0.,
+            Self::Methyl => C + 2. * H,
+            Self::Ethyl => 2. * C + 4. * H,
+            Self::Triacylglycerol => C + 2. / 3. * H,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{C4U0, C16U0, fatty_acid};
+
+    #[test]
+    fn response_factor() {
+        assert_eq!(C16U0.effective_carbons(Derivative::Methyl), 16);
+        assert_eq!(C16U0.effective_carbons(Derivative::Acid), 15);
+        assert_eq!(FattyAcid::new(0).effective_carbons(Derivative::Methyl), 1);
+        assert_eq!(C16U0.response_factor(Derivative::Methyl, &C16U0), 1.0);
+        // Short chain FAMEs are under-reported by the FID,
+        // (102.07 / 4) / (270.26 / 16)
+        let factor = C4U0.response_factor(Derivative::Methyl, &C16U0);
+        assert!((factor - 1.51).abs() < 0.01, "{factor}");
+        let factor = fatty_acid!(22;4,7,10,13,16,19).response_factor(Derivative::Methyl, &C16U0);
+        // (342.26 / 22) / (270.26 / 16)
+        assert!((factor - 0.92).abs() < 0.01, "{factor}");
+    }
+
+    #[test]
+    fn conversion_factors() {
+        // AOAC 996.06
+        let factor = |fatty_acid: &FattyAcid, from, to| fatty_acid.mass(to) / fatty_acid.mass(from);
+        let tag = Derivative::Triacylglycerol;
+        let fame = Derivative::Methyl;
+        assert!((factor(&FattyAcid::new(11), tag, fame) - 1.0067).abs() < 1e-3);
+        assert!((factor(&C16U0, fame, Derivative::Acid) - 0.9481).abs() < 1e-3);
+        assert!((factor(&C16U0, fame, tag) - 0.9950).abs() < 1e-3);
+    }
+}
@@ -1,5 +1,5 @@
-use self::{index::Notation, isomerism::Elision};
-use crate::fatty_acid::FattyAcid;
+pub use self::{index::Notation, isomerism::Elision};
+use crate::fatty_acid::{FattyAcid, Unsaturated};
 use serde::Serialize;
 use std::{
     borrow::Borrow,
@@ -14,6 +14,8 @@ pub const ID: Options = Options {
     },
     notation: Notation::Prefix,
     elision: Elision::Explicit,
+    alternate: false,
+    width: None,
 };
 
 pub const COMMON: Options = Options {
@@ -24,6 +26,8 @@ pub const COMMON: Options = Options {
     },
     notation: Notation::Suffix,
     elision: Elision::Implicit,
+    alternate: false,
+    width: None,
 };
 
 /// Display with options
@@ -67,6 +71,10 @@ pub struct Options {
     pub separators: Separators,
     pub notation: Notation,
     pub elision: Elision,
+    /// Alternate form, the same as `{:#}`
+    pub alternate: bool,
+    /// Zero padding width, the same as `{:0width$}`
+    pub width: Option<usize>,
 }
 
 /// Separators
@@ -126,38 +134,30 @@ pub struct Separators {
 impl<T: Borrow<FattyAcid>> fmt::Display for Display<T> {
     fn fmt(&self, f: &mut Formatter) -> fmt::Result {
         let fatty_acid = self.fatty_acid.borrow();
+        let width = self.options.width;
         f.write_str(self.options.separators.c)?;
-        fmt::Display::fmt(&fatty_acid.carbons, f)?;
+        fmt::Display::fmt(&Padded(fatty_acid.carbons, width), f)?;
         f.write_str(self.options.separators.u)?;
-        fmt::Display::fmt(&fatty_acid.unsaturated.len(), f)?;
-        if f.alternate() {
+        fmt::Display::fmt(&Padded(fatty_acid.unsaturated.len(), width), f)?;
+        if f.alternate() || self.options.alternate {
+            let index = |unsaturated: &Unsaturated| {
+                Some(index::Display::new(
+                    Padded(unsaturated.index?, width),
+                    isomerism::Display::new(unsaturated.isomerism, self.options.elision),
+                    self.options.notation,
+                ))
+            };
             let mut iter = fatty_acid.unsaturated.iter();
             if let Some(unsaturated) = iter.next() {
-                if let Some(index) = unsaturated.index {
+                if let Some(index) = index(unsaturated) {
                     f.write_str(self.options.separators.i[0])?;
                     fmt::Display::fmt(&index, f)?;
                 }
-                // fmt::Display::fmt(
-                //     &index::Display::new(
-                //         unsaturated.index.map(|index| index + 1),
-                //         isomerism::Display::new(unsaturated.isomerism, self.options.elision),
-                //         self.options.notation,
-                //     ),
-                //     f,
-                // )?;
                 for unsaturated in iter {
                     f.write_str(self.options.separators.i[1])?;
-                    if let Some(index) = unsaturated.index {
+                    if let Some(index) = index(unsaturated) {
                         fmt::Display::fmt(&index, f)?;
                     }
-                    // fmt::Display::fmt(
-                    //     &index::Display::new(
-                    //         unsaturated.index + 1,
-                    //         isomerism::Display::new(unsaturated.isomerism, self.options.elision),
-                    //         self.options.notation,
-                    //     ),
-                    //     f,
-                    // )?;
                 }
             }
         }
@@ -165,20 +165,34 @@ impl<T: Borrow<FattyAcid>> fmt::Display for Display<T> {
     }
 }
 
+/// Zero padded number
+///
+/// The formatter width takes precedence over the options width.
+struct Padded<T>(T, Option<usize>);
+
+impl<T: fmt::Display> fmt::Display for Padded<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self.1 {
+            Some(width) if f.width().is_none() => write!(f, "{:0width$}", self.0),
+            _ => fmt::Display::fmt(&self.0, f),
+        }
+    }
+}
+
 mod index {
     use super::isomerism;
     use serde::{Deserialize, Serialize};
     use std::fmt::{self, Formatter};
 
     /// Index display
-    pub(super) struct Display {
-        index: usize,
+    pub(super) struct Display<T> {
+        index: T,
         isomerism: isomerism::Display,
         notation: Notation,
     }
 
-    impl Display {
-        pub(super) fn new(index: usize, isomerism: isomerism::Display, notation: Notation) -> Self {
+    impl<T> Display<T> {
+        pub(super) fn new(index: T, isomerism: isomerism::Display, notation: Notation) -> Self {
             Self {
                 index,
                 isomerism,
@@ -187,7 +201,7 @@ mod index {
         }
     }
 
-    impl fmt::Display for Display {
+    impl<T: fmt::Display> fmt::Display for Display<T> {
         fn fmt(&self, f: &mut Formatter) -> fmt::Result {
             match self.notation {
                 Notation::Prefix => {
@@ -218,12 +232,12 @@ mod isomerism {
 
     /// Display isomerism
     pub(super) struct Display {
-        pub(super) isomerism: Isomerism,
+        pub(super) isomerism: Option<Isomerism>,
         pub(super) elision: Elision,
     }
 
     impl Display {
-        pub(super) fn new(isomerism: Isomerism, elision: Elision) -> Self {
+        pub(super) fn new(isomerism: Option<Isomerism>, elision: Elision) -> Self {
             Self { isomerism, elision }
         }
     }
@@ -231,16 +245,10 @@ mod isomerism {
     impl fmt::Display for Display {
         fn fmt(&self, f: &mut Formatter) -> fmt::Result {
             match self.isomerism {
-                Isomerism::Cis => {
-                    if self.elision == Elision::Explicit {
-                        f.write_char('c')?;
-                    }
-                }
-                Isomerism::Trans => {
-                    f.write_char('t')?;
-                }
+                Some(Isomerism::Cis) if self.elision == Elision::Explicit => f.write_char('c'),
+                Some(Isomerism::Trans) => f.write_char('t'),
+                _ => Ok(()),
             }
-            Ok(())
         }
     }
 
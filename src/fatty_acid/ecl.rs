@@ -0,0 +1,217 @@
+use crate::fatty_acid::FattyAcid;
+use serde::{Deserialize, Serialize};
+use std::{
+    error,
+    fmt::{self, Formatter},
+};
+
+/// ECL (Equivalent chain length) calibration
+///
+/// Retention times of saturated standards, e.g. [`C8U0`](super::C8U0) …
+/// [`C24U0`](super::C24U0). Retention times between two standards are
+/// interpolated, outside the standards they are extrapolated from the nearest
+/// two.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "Points")]
+pub struct Calibration {
+    /// Carbons and retention times, sorted by carbons
+    points: Vec<(u8, f64)>,
+    mode: Mode,
+}
+
+/// Unchecked calibration, deserialized through [`Calibration::new`]
+#[derive(Deserialize)]
+struct Points {
+    points: Vec<(u8, f64)>,
+    mode: Mode,
+}
+
+impl TryFrom<Points> for Calibration {
+    type Error = Error;
+
+    fn try_from(value: Points) -> Result<Self, Self::Error> {
+        let standards = value
+            .points
+            .into_iter()
+            .map(|(carbons, retention_time)| (FattyAcid::new(carbons), retention_time));
+        Self::new(standards, value.mode)
+    }
+}
+
+impl Calibration {
+    pub fn new(
+        standards: impl IntoIterator<Item = (FattyAcid, f64)>,
+        mode: Mode,
+    ) -> Result<Self, Error> {
+        let mut points = Vec::new();
+        for (fatty_acid, retention_time) in standards {
+            if !fatty_acid.unsaturated.is_empty() {
+                return Err(Error::Unsaturated(fatty_acid));
+            }
+            if matches!(mode, Mode::Isothermal { dead_time } if retention_time <= dead_time) {
+                return Err(Error::DeadTime(fatty_acid));
+            }
+            points.push((fatty_acid.carbons, retention_time));
+        }
+        if points.len() < 2 {
+            return Err(Error::Standards);
+        }
+        points.sort_by_key(|&(carbons, _)| carbons);
+        for window in points.windows(2) {
+            if window[0].0 == window[1].0 || window[0].1 >= window[1].1 {
+                return Err(Error::Order(FattyAcid::new(window[1].0)));
+            }
+        }
+        Ok(Self { points, mode })
+    }
+
+    pub fn mode(&self) -> Mode {
+        self.mode
+    }
+
+    /// ECL of the retention time
+    ///
+    /// `None` if the retention time is not after the dead time.
+    pub fn ecl(&self, retention_time: f64) -> Option<f64> {
+        let transform = |retention_time: f64| match self.mode {
+            Mode::Isothermal { dead_time } => {
+                (retention_time > dead_time).then(|| (retention_time - dead_time).ln())
+            }
+            Mode::Programmed => Some(retention_time),
+        };
+        let index = self.points[1..self.points.len() - 1]
+            .iter()
+            .take_while(|&&(_, time)| time <= retention_time)
+            .count();
+        let (from, to) = (self.points[index], self.points[index + 1]);
+        let (x, x0, x1) = (
+            transform(retention_time)?,
+            transform(from.1)?,
+            transform(to.1)?,
+        );
+        Some(from.0 as f64 + (to.0 - from.0) as f64 * (x - x0) / (x1 - x0))
+    }
+}
+
+/// ECL mode
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Mode {
+    /// Isothermal, log-linear in the adjusted retention time `t - t0`
+    Isothermal { dead_time: f64 },
+    /// Temperature programmed, linear in the retention time (van den Dool &
+    /// Kratz, 1963)
+    Programmed,
+}
+
+/// ECL calibration error
+#[derive(Clone, Debug, PartialEq)]
+pub enum Error {
+    /// Fewer than two standards
+    Standards,
+    /// Unsaturated standard
+    Unsaturated(FattyAcid),
+    /// Duplicate standard or retention time not increasing with carbons
+    Order(FattyAcid),
+    /// Retention time not after the dead time
+    DeadTime(FattyAcid),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Standards => f.write_str("expected at least two standards"),
+            Self::Unsaturated(fatty_acid) => {
+                write!(f, "unsaturated standard C{}", fatty_acid.carbons)
+            }
+            Self::Order(fatty_acid) => write!(
+                f,
+                "retention time of C{} does not increase with carbons",
+                fatty_acid.carbons,
+            ),
+            Self::DeadTime(fatty_acid) => write!(
+                f,
+                "retention time of C{} is not after the dead time",
+                fatty_acid.carbons,
+            ),
+        }
+    }
+}
+
+impl error::Error for Error {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{C14U0, C16U0, C18U0, C20U0, fatty_acid};
+
+    #[test]
+    fn programmed() {
+        let calibration = Calibration::new(
+            [(C20U0, 14.0), (C16U0, 10.0), (C18U0, 12.0)],
+            Mode::Programmed,
+        )
+        .unwrap();
+        assert_eq!(calibration.ecl(12.0), Some(18.0));
+        assert_eq!(calibration.ecl(11.0), Some(17.0));
+        assert_eq!(calibration.ecl(12.5), Some(18.5));
+        // Extrapolated
+        assert_eq!(calibration.ecl(9.0), Some(15.0));
+        assert_eq!(calibration.ecl(16.0), Some(22.0));
+    }
+
+    #[test]
+    fn isothermal() {
+        let calibration = Calibration::new(
+            [(C14U0, 3.0), (C16U0, 5.0), (C18U0, 9.0)],
+            Mode::Isothermal { dead_time: 1.0 },
+        )
+        .unwrap();
+        // Adjusted retention times 2, 4, 8
+        let ecl = calibration.ecl(1.0 + 4.0 * 2f64.sqrt()).unwrap();
+        assert!((ecl - 17.0).abs() < 1e-12);
+        let ecl = calibration.ecl(4.0).unwrap();
+        assert!((ecl - (14.0 + 2.0 * 1.5f64.log2())).abs() < 1e-12);
+        assert_eq!(calibration.ecl(0.5), None);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            Calibration::new([(C16U0, 10.0)], Mode::Programmed),
+            Err(Error::Standards),
+        );
+        assert_eq!(
+            Calibration::new([(C16U0, 10.0), (fatty_acid!(18;9), 12.0)], Mode::Programmed),
+            Err(Error::Unsaturated(fatty_acid!(18;9))),
+        );
+        assert_eq!(
+            Calibration::new([(C16U0, 10.0), (C18U0, 9.0)], Mode::Programmed),
+            Err(Error::Order(C18U0)),
+        );
+        assert_eq!(
+            Calibration::new(
+                [(C16U0, 10.0), (C18U0, 12.0)],
+                Mode::Isothermal { dead_time: 10.0 },
+            ),
+            Err(Error::DeadTime(C16U0)),
+        );
+    }
+
+    #[test]
+    fn ron() {
+        let calibration =
+            Calibration::new([(C16U0, 10.0), (C18U0, 12.0)], Mode::Programmed).unwrap();
+        let text = ron::to_string(&calibration).unwrap();
+        assert_eq!(ron::from_str::<Calibration>(&text), Ok(calibration));
+        // Unsorted points are sorted
+        let calibration =
+            ron::from_str::<Calibration>("(points: [(18, 12.0), (16, 10.0)], mode: Programmed)")
+                .unwrap();
+        assert_eq!(calibration.ecl(11.0), Some(17.0));
+        assert!(ron::from_str::<Calibration>("(points: [(16, 10.0)], mode: Programmed)").is_err());
+        assert!(
+            ron::from_str::<Calibration>("(points: [(16, 10.0), (18, 9.0)], mode: Programmed)")
+                .is_err()
+        );
+    }
+}
@@ -0,0 +1,219 @@
+use crate::fatty_acid::FattyAcid;
+use serde::{Deserialize, Serialize};
+
+/// Retention library of one column phase
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct Library {
+    pub entries: Vec<Entry>,
+}
+
+/// Library entry
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Entry {
+    pub fatty_acid: FattyAcid,
+    /// ECL (Equivalent chain length)
+    pub ecl: f64,
+}
+
+/// Candidate of a peak
+#[derive(Clone, Debug, PartialEq)]
+pub struct Candidate {
+    pub fatty_acid: FattyAcid,
+    /// Library ECL
+    pub ecl: f64,
+    /// Peak ECL minus library ECL
+    pub delta: f64,
+    /// `1 - |delta| / tolerance`, 1 for an exact match and 0 at the tolerance
+    pub score: f64,
+}
+
+/// Assignment of peaks to library entries
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Assignment {
+    /// Closest pairs first
+    Greedy,
+    /// Maximum total score
+    #[default]
+    Optimal,
+}
+
+impl Library {
+    /// Candidates within the tolerance, the best first
+    ///
+    /// A tolerance of 0 or less matches exact ECLs only.
+    pub fn candidates(&self, ecl: f64, tolerance: f64) -> Vec<Candidate> {
+        let mut candidates = self
+            .entries
+            .iter()
+            .filter_map(|entry| entry.candidate(ecl, tolerance))
+            .collect::<Vec<_>>();
+        candidates.sort_by(|left, right| right.score.total_cmp(&left.score));
+        candidates
+    }
+
+    /// Assign peaks to library entries
+    ///
+    /// Each entry identifies at most one peak. A tolerance of 0 or less
+    /// matches exact ECLs only.
+    pub fn assign(
+        &self,
+        ecls: &[f64],
+        tolerance: f64,
+        assignment: Assignment,
+    ) -> Vec<Option<Candidate>> {
+        match assignment {
+            Assignment::Greedy => self.greedy(ecls, tolerance),
+            Assignment::Optimal => self.optimal(ecls, tolerance),
+        }
+    }
+
+    fn greedy(&self, ecls: &[f64], tolerance: f64) -> Vec<Option<Candidate>> {
+        let mut pairs = Vec::new();
+        for (peak, &ecl) in ecls.iter().enumerate() {
+            for (index, entry) in self.entries.iter().enumerate() {
+                if let Some(candidate) = entry.candidate(ecl, tolerance) {
+                    pairs.push((peak, index, candidate));
+                }
+            }
+        }
+        pairs.sort_by(|left, right| right.2.score.total_cmp(&left.2.score));
+        let mut assigned = vec![None; ecls.len()];
+        let mut used = vec![false; self.entries.len()];
+        for (peak, index, candidate) in pairs {
+            if assigned[peak].is_none() && !used[index] {
+                assigned[peak] = Some(candidate);
+                used[index] = true;
+            }
+        }
+        assigned
+    }
+
+    /// An optimal matching of points on a line does not cross, so the peaks
+    /// and the entries are aligned in ECL order.
+    fn optimal(&self, ecls: &[f64], tolerance: f64) -> Vec<Option<Candidate>> {
+        let mut peaks = (0..ecls.len()).collect::<Vec<_>>();
+        peaks.sort_by(|&left, &right| ecls[left].total_cmp(&ecls[right]));
+        let mut entries = self.entries.iter().collect::<Vec<_>>();
+        entries.sort_by(|left, right| left.ecl.total_cmp(&right.ecl));
+        let (rows, columns) = (peaks.len(), entries.len());
+        let mut scores = vec![vec![0.0f64; columns + 1]; rows + 1];
+        for row in 1..=rows {
+            for column in 1..=columns {
+                let mut score = scores[row - 1][column].max(scores[row][column - 1]);
+                if let Some(candidate) =
+                    entries[column - 1].candidate(ecls[peaks[row - 1]], tolerance)
+                {
+                    score = score.max(scores[row - 1][column - 1] + candidate.score);
+                }
+                scores[row][column] = score;
+            }
+        }
+        let mut assigned = vec![None; ecls.len()];
+        let (mut row, mut column) = (rows, columns);
+        while row > 0 && column > 0 {
+            if scores[row][column] == scores[row - 1][column] {
+                row -= 1;
+            } else if scores[row][column] == scores[row][column - 1] {
+                column -= 1;
+            } else {
+                assigned[peaks[row - 1]] =
+                    entries[column - 1].candidate(ecls[peaks[row - 1]], tolerance);
+                row -= 1;
+                column -= 1;
+            }
+        }
+        assigned
+    }
+}
+
+impl Entry {
+    fn candidate(&self, ecl: f64, tolerance: f64) -> Option<Candidate> {
+        let delta = ecl - self.ecl;
+        if tolerance <= 0.0 {
+            return (delta == 0.0).then(|| Candidate {
+                fatty_acid: self.fatty_acid.clone(),
+                ecl: self.ecl,
+                delta,
+                score: 1.0,
+            });
+        }
+        (delta.abs() <= tolerance).then(|| Candidate {
+            fatty_acid: self.fatty_acid.clone(),
+            ecl: self.ecl,
+            delta,
+            score: 1.0 - delta.abs() / tolerance,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    fn library() -> Library {
+        Library {
+            entries: [
+                (fatty_acid!(16), 16.0),
+                (fatty_acid!(18), 18.0),
+                (fatty_acid!(18;-9), 18.15),
+                (fatty_acid!(18;9), 18.22),
+            ]
+            .into_iter()
+            .map(|(fatty_acid, ecl)| Entry { fatty_acid, ecl })
+            .collect(),
+        }
+    }
+
+    #[test]
+    fn candidates() {
+        let candidates = library().candidates(18.2, 0.1);
+        assert_eq!(candidates.len(), 2);
+        assert_eq!(candidates[0].fatty_acid, fatty_acid!(18;9));
+        assert_eq!(candidates[1].fatty_acid, fatty_acid!(18;-9));
+        assert!((candidates[0].score - 0.8).abs() < 1e-9);
+        assert!(library().candidates(17.0, 0.1).is_empty());
+    }
+
+    #[test]
+    fn exact() {
+        for tolerance in [0.0, -0.1] {
+            let candidates = library().candidates(18.22, tolerance);
+            assert_eq!(candidates.len(), 1);
+            assert_eq!(candidates[0].fatty_acid, fatty_acid!(18;9));
+            assert_eq!(candidates[0].score, 1.0);
+            assert!(library().candidates(18.2, tolerance).is_empty());
+        }
+        let assigned = library().assign(&[16.0, 18.2], 0.0, Assignment::Optimal);
+        assert_eq!(assigned[0].as_ref().unwrap().fatty_acid, fatty_acid!(16));
+        assert_eq!(assigned[1], None);
+    }
+
+    #[test]
+    fn assign() {
+        let library = library();
+        let ecls = [18.28, 16.01, 18.2, 20.0];
+        let fatty_acids = |assignment| {
+            library
+                .assign(&ecls, 0.1, assignment)
+                .into_iter()
+                .map(|candidate| Some(candidate?.fatty_acid))
+                .collect::<Vec<_>>()
+        };
+        // Greedy takes the closest 18.2 ↔ 18:1Δ9 pair first and leaves 18.28
+        // unassigned.
+        assert_eq!(
+            fatty_acids(Assignment::Greedy),
+            [None, Some(fatty_acid!(16)), Some(fatty_acid!(18;9)), None],
+        );
+        assert_eq!(
+            fatty_acids(Assignment::Optimal),
+            [
+                Some(fatty_acid!(18;9)),
+                Some(fatty_acid!(16)),
+                Some(fatty_acid!(18;-9)),
+                None,
+            ],
+        );
+    }
+}
@@ -0,0 +1,262 @@
+use crate::fatty_acid::{
+    FattyAcid,
+    chain_length::{Boundaries, ChainLength, Parity},
+    composition::Composition,
+};
+use serde::{Deserialize, Serialize};
+
+/// Registry of composition indices
+///
+/// The [`Default`] registry holds the built-in indices (IA, IT, HH, HPI, UI,
+/// FLQ, PUFA/SFA, n-6/n-3, DFA, OFA, NVI, COX, PI, Δ9 desaturation). Custom
+/// sets are loaded from RON, see [`Registry::from_ron`].
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Registry {
+    pub indices: Vec<Index>,
+}
+
+impl Registry {
+    pub fn from_ron(text: &str) -> ron::error::SpannedResult<Self> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(text)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default()
+                .extensions(ron::extensions::Extensions::IMPLICIT_SOME),
+        )
+    }
+
+    /// Index by name
+    pub fn get(&self, name: &str) -> Option<&Index> {
+        self.indices.iter().find(|index| index.name == name)
+    }
+}
+
+impl Default for Registry {
+    fn default() -> Self {
+        Self::from_ron(include_str!("Indices.ron")).expect("deserialize Indices.ron")
+    }
+}
+
+/// Composition index
+///
+/// The formula is written in RON or parsed from text, see [`parse`](parse::parse).
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Index {
+    pub name: String,
+    pub description: String,
+    pub formula: Formula,
+}
+
+/// Formula
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Formula {
+    Constant(f64),
+    Sum(Term),
+    Add(Vec<Formula>),
+    Sub(Box<Formula>, Box<Formula>),
+    Mul(Vec<Formula>),
+    Div(Box<Formula>, Box<Formula>),
+    /// If the condition holds, then the first formula, else the second.
+    If(Box<Condition>, Box<Formula>, Box<Formula>),
+}
+
+impl Formula {
+    /// Evaluate the composition
+    pub fn evaluate(&self, composition: &Composition) -> f64 {
+        match self {
+            Self::Constant(constant) => *constant,
+            Self::Sum(term) => term.evaluate(composition),
+            Self::Add(formulas) => formulas
+                .iter()
+                .map(|formula| formula.evaluate(composition))
+                .sum(),
+            Self::Sub(lhs, rhs) => lhs.evaluate(composition) - rhs.evaluate(composition),
+            Self::Mul(formulas) => formulas
+                .iter()
+                .map(|formula| formula.evaluate(composition))
+                .product(),
+            Self::Div(lhs, rhs) => lhs.evaluate(composition) / rhs.evaluate(composition),
+            Self::If(condition, then, otherwise) => {
+                if condition.evaluate(composition) {
+                    then.evaluate(composition)
+                } else {
+                    otherwise.evaluate(composition)
+                }
+            }
+        }
+    }
+}
+
+/// Condition
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Condition {
+    Compare(Formula, Comparison, Formula),
+    And(Box<Condition>, Box<Condition>),
+    Or(Box<Condition>, Box<Condition>),
+    Not(Box<Condition>),
+}
+
+impl Condition {
+    /// Evaluate the composition
+    pub fn evaluate(&self, composition: &Composition) -> bool {
+        match self {
+            Self::Compare(lhs, comparison, rhs) => {
+                let lhs = lhs.evaluate(composition);
+                let rhs = rhs.evaluate(composition);
+                match comparison {
+                    Comparison::Lt => lhs < rhs,
+                    Comparison::Le => lhs <= rhs,
+                    Comparison::Gt => lhs > rhs,
+                    Comparison::Ge => lhs >= rhs,
+                    Comparison::Eq => lhs == rhs,
+                    Comparison::Ne => lhs != rhs,
+                }
+            }
+            Self::And(lhs, rhs) => lhs.evaluate(composition) && rhs.evaluate(composition),
+            Self::Or(lhs, rhs) => lhs.evaluate(composition) || rhs.evaluate(composition),
+            Self::Not(condition) => !condition.evaluate(composition),
+        }
+    }
+}
+
+/// Comparison
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+pub enum Comparison {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+    Ne,
+}
+
+/// Term
+///
+/// The sum of the values of a class of fatty acids.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Term {
+    /// ΣFA
+    Total,
+    /// ΣSFA
+    Sfa,
+    /// ΣUFA
+    Ufa,
+    /// ΣMUFA
+    Mufa,
+    /// ΣPUFA
+    Pufa,
+    /// Fatty acids having exactly `n` unsaturated bonds
+    Enoics(u8),
+    /// ω-n
+    N(u8),
+    /// PUFA ω-n
+    PufaN(u8),
+    /// TFA, including conjugated isomers if set
+    Tfa(bool),
+    /// Σ(unsaturated bonds * value)
+    Bonds,
+    /// Chain length class, the default [`Boundaries`] if omitted
+    ChainLength {
+        class: ChainLength,
+        #[serde(default)]
+        boundaries: Boundaries,
+    },
+    /// Odd or even chain fatty acids
+    Parity(Parity),
+    /// Fatty acids at the positions of the pattern, see
+    /// [`FattyAcid::positional`]
+    Acid(FattyAcid),
+}
+
+impl Term {
+    /// Evaluate the composition
+    pub fn evaluate(&self, composition: &Composition) -> f64 {
+        composition
+            .iter()
+            .map(|(fatty_acid, value)| self.weight(fatty_acid) * value)
+            .sum()
+    }
+
+    /// Weight of the fatty acid in the sum, 0 or 1 except for
+    /// [`Bonds`](Self::Bonds)
+    pub fn weight(&self, fatty_acid: &FattyAcid) -> f64 {
+        let unsaturated = fatty_acid.unsaturated.len();
+        let filtered = match self {
+            Self::Total => true,
+            Self::Sfa => unsaturated == 0,
+            Self::Ufa => unsaturated != 0,
+            Self::Mufa => unsaturated == 1,
+            Self::Pufa => unsaturated > 1,
+            Self::Enoics(n) => unsaturated == *n as usize,
+            Self::N(n) => fatty_acid.omega() == Some(*n),
+            Self::PufaN(n) => unsaturated > 1 && fatty_acid.omega() == Some(*n),
+            Self::Tfa(conjugated) => {
+                fatty_acid.is_trans() && (*conjugated || !fatty_acid.is_conjugated())
+            }
+            Self::Bonds => return unsaturated as f64,
+            Self::ChainLength { class, boundaries } => {
+                fatty_acid.chain_length_class(boundaries) == *class
+            }
+            Self::Parity(parity) => fatty_acid.parity() == *parity,
+            Self::Acid(pattern) => fatty_acid.positional(pattern),
+        };
+        if filtered { 1.0 } else { 0.0 }
+    }
+}
+
+pub mod parse;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn ron() {
+        let registry = Registry::default();
+        let text = registry.to_ron().unwrap();
+        assert_eq!(Registry::from_ron(&text).unwrap(), registry);
+        assert!(Registry::from_ron("[(name: \"\")]").is_err());
+        // Boundaries default if omitted
+        assert_eq!(
+            ron::from_str::<Term>("ChainLength(class: Medium)").unwrap(),
+            Term::ChainLength {
+                class: ChainLength::Medium,
+                boundaries: Boundaries::default(),
+            },
+        );
+    }
+
+    #[test]
+    fn evaluate() {
+        let composition = Composition::from_iter([
+            (fatty_acid!(12), 2.0),
+            (fatty_acid!(14), 8.0),
+            (fatty_acid!(16), 24.0),
+            (fatty_acid!(18), 12.0),
+            (fatty_acid!(18;9), 30.0),
+            (fatty_acid!(18;9,12), 13.0),
+        ]);
+        let formula = |text: &str| parse::parse(text).unwrap().evaluate(&composition);
+        assert_eq!(formula("C18:1 / C18:0"), 2.5);
+        assert_eq!(formula("if PUFA > 10 then UI else 0"), 30.0 + 26.0);
+        // C12 and C14 are medium chain with the C8/C16/C22 boundaries
+        let term = Term::ChainLength {
+            class: ChainLength::Medium,
+            boundaries: Boundaries {
+                medium: 8,
+                long: 16,
+                very_long: 22,
+            },
+        };
+        assert_eq!(term.evaluate(&composition), 10.0);
+        assert_eq!(Term::Parity(Parity::Even).evaluate(&composition), 89.0);
+        assert_eq!(Term::Parity(Parity::Odd).evaluate(&composition), 0.0);
+    }
+}
@@ -0,0 +1,452 @@
+use super::{Comparison, Condition, Formula, Term};
+use crate::fatty_acid::{
+    FattyAcid,
+    chain_length::{Boundaries, ChainLength, Parity},
+    fatty_acid,
+    parse::{self, Format},
+};
+use std::{
+    error,
+    fmt::{self, Formatter},
+    ops::Range,
+    str::FromStr,
+};
+
+impl FromStr for Formula {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse(text)
+    }
+}
+
+/// Parse a formula
+///
+/// ```text
+/// formula     = "if" condition "then" formula "else" formula | additive
+/// condition   = conjunction ("or" conjunction)*
+/// conjunction = negation ("and" negation)*
+/// negation    = "not" negation | additive ("<" | "<=" | ">" | ">=" | "==" | "!=") additive
+/// additive    = product (("+" | "-") product)*
+/// product     = unary (("*" | "/") unary)*
+/// unary       = "-" unary | number | fatty acid | name | "(" formula ")"
+/// ```
+///
+/// Fatty acids are written as in [`Format::Auto`] (`C12:0`, `18:1Δ9`,
+/// `C18:2n-6`, `c18u1c9`) and match at the positions of the pattern, see
+/// [`FattyAcid::positional`]. Names are named acids (`OA`, `LA`, `GLA`, `ALA`,
+/// `ARA`, `EPA`, `DPA`, `DHA`), the same lookups as the polars `FindByName`,
+/// and class sums (`SUM`, `SFA`, `UFA`, `MUFA`, `PUFA`, `TFA`, `TFA_CLA`, `UI`,
+/// `SCFA`, `MCFA`, `LCFA`, `VLCFA`, `OCFA`, `ECFA`, `nN` for ω-N and `PUFA_nN`
+/// for PUFA ω-N). The chain length classes use the default [`Boundaries`],
+/// see [`parse_with`].
+pub fn parse(text: &str) -> Result<Formula, Error> {
+    parse_with(text, &Boundaries::default())
+}
+
+/// Parse a formula with the chain length class `boundaries`
+pub fn parse_with(text: &str, boundaries: &Boundaries) -> Result<Formula, Error> {
+    let tokens = lex(text)?;
+    let mut parser = Parser {
+        tokens,
+        position: 0,
+        end: text.len(),
+        boundaries,
+    };
+    let formula = parser.formula()?;
+    if let Some((_, span)) = parser.tokens.get(parser.position) {
+        return Err(Error {
+            span: span.clone(),
+            kind: ErrorKind::Expected("end"),
+        });
+    }
+    Ok(formula)
+}
+
+/// Formula parse error
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    /// Byte span in the parsed text
+    pub span: Range<usize>,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at {}..{}", self.kind, self.span.start, self.span.end)
+    }
+}
+
+impl error::Error for Error {}
+
+/// Formula parse error kind
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// Expected token
+    Expected(&'static str),
+    /// Unexpected character
+    Character(char),
+    /// Invalid number
+    Number,
+    /// Unknown name
+    Name(String),
+    /// Invalid fatty acid
+    FattyAcid(parse::ErrorKind),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Expected(expected) => write!(f, "expected {expected}"),
+            Self::Character(character) => write!(f, "unexpected character {character:?}"),
+            Self::Number => f.write_str("invalid number"),
+            Self::Name(name) => write!(f, "unknown name {name:?}"),
+            Self::FattyAcid(kind) => write!(f, "invalid fatty acid, {kind}"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token<'a> {
+    Number(f64),
+    FattyAcid(FattyAcid),
+    Name(&'a str),
+    Symbol(&'static str),
+}
+
+const SYMBOLS: [&str; 12] = [
+    "<=", ">=", "==", "!=", "<", ">", "+", "-", "*", "/", "(", ")",
+];
+
+fn lex(text: &str) -> Result<Vec<(Token<'_>, Range<usize>)>, Error> {
+    let mut tokens = Vec::new();
+    let mut offset = 0;
+    while let Some(char) = text[offset..].chars().next() {
+        let rest = &text[offset..];
+        let start = offset;
+        if char.is_whitespace() {
+            offset += char.len_utf8();
+            continue;
+        }
+        let token =
+            if let Some(symbol) = SYMBOLS.into_iter().find(|symbol| rest.starts_with(symbol)) {
+                offset += symbol.len();
+                Token::Symbol(symbol)
+            } else if is_fatty_acid(rest) {
+                offset += fatty_acid_length(rest);
+                let fatty_acid =
+                    parse::parse(&text[start..offset], Format::Auto).map_err(|error| Error {
+                        span: start..offset,
+                        kind: ErrorKind::FattyAcid(error.kind),
+                    })?;
+                Token::FattyAcid(fatty_acid)
+            } else if char.is_ascii_digit() || char == '.' {
+                offset += rest
+                    .find(|char: char| !char.is_ascii_digit() && char != '.')
+                    .unwrap_or(rest.len());
+                let number = text[start..offset].parse().map_err(|_| Error {
+                    span: start..offset,
+                    kind: ErrorKind::Number,
+                })?;
+                Token::Number(number)
+            } else if char.is_alphabetic() || char == '_' {
+                offset += rest
+                    .find(|char: char| !char.is_alphanumeric() && char != '_')
+                    .unwrap_or(rest.len());
+                Token::Name(&text[start..offset])
+            } else {
+                return Err(Error {
+                    span: start..start + char.len_utf8(),
+                    kind: ErrorKind::Character(char),
+                });
+            };
+        tokens.push((token, start..offset));
+    }
+    Ok(tokens)
+}
+
+/// `C18…`, `18:…` or `c18u…`
+fn is_fatty_acid(text: &str) -> bool {
+    fn digits(text: &str) -> (usize, &str) {
+        let length = text
+            .find(|char: char| !char.is_ascii_digit())
+            .unwrap_or(text.len());
+        (length, &text[length..])
+    }
+    if let Some(rest) = text.strip_prefix('C') {
+        let (length, _) = digits(rest);
+        length > 0
+    } else if let Some(rest) = text.strip_prefix('c') {
+        let (length, rest) = digits(rest);
+        length > 0 && rest.starts_with('u')
+    } else {
+        let (length, rest) = digits(text);
+        length > 0 && rest.starts_with(':')
+    }
+}
+
+/// Alphanumerics, `:`, `Δ`, `ω`, `,` and `-` after `n`, `ω` or `w`.
+fn fatty_acid_length(text: &str) -> usize {
+    let mut previous = None;
+    for (offset, char) in text.char_indices() {
+        let part = char.is_ascii_alphanumeric()
+            || matches!(char, ':' | 'Δ' | 'ω' | ',')
+            || char == '-' && matches!(previous, Some('n' | 'ω' | 'w'));
+        if !part {
+            return offset;
+        }
+        previous = Some(char);
+    }
+    text.len()
+}
+
+struct Parser<'a> {
+    tokens: Vec<(Token<'a>, Range<usize>)>,
+    position: usize,
+    end: usize,
+    boundaries: &'a Boundaries,
+}
+
+impl<'a> Parser<'a> {
+    fn formula(&mut self) -> Result<Formula, Error> {
+        if self.eat(&Token::Name("if")) {
+            let condition = self.condition()?;
+            self.expect(Token::Name("then"), "then")?;
+            let then = self.formula()?;
+            self.expect(Token::Name("else"), "else")?;
+            let otherwise = self.formula()?;
+            return Ok(Formula::If(
+                Box::new(condition),
+                Box::new(then),
+                Box::new(otherwise),
+            ));
+        }
+        self.additive()
+    }
+
+    fn condition(&mut self) -> Result<Condition, Error> {
+        let mut condition = self.conjunction()?;
+        while self.eat(&Token::Name("or")) {
+            condition = Condition::Or(Box::new(condition), Box::new(self.conjunction()?));
+        }
+        Ok(condition)
+    }
+
+    fn conjunction(&mut self) -> Result<Condition, Error> {
+        let mut condition = self.negation()?;
+        while self.eat(&Token::Name("and")) {
+            condition = Condition::And(Box::new(condition), Box::new(self.negation()?));
+        }
+        Ok(condition)
+    }
+
+    fn negation(&mut self) -> Result<Condition, Error> {
+        if self.eat(&Token::Name("not")) {
+            return Ok(Condition::Not(Box::new(self.negation()?)));
+        }
+        let lhs = self.additive()?;
+        let comparison = match self.peek() {
+            Some(Token::Symbol("<")) => Comparison::Lt,
+            Some(Token::Symbol("<=")) => Comparison::Le,
+            Some(Token::Symbol(">")) => Comparison::Gt,
+            Some(Token::Symbol(">=")) => Comparison::Ge,
+            Some(Token::Symbol("==")) => Comparison::Eq,
+            Some(Token::Symbol("!=")) => Comparison::Ne,
+            _ => return Err(self.error(ErrorKind::Expected("comparison"))),
+        };
+        self.position += 1;
+        let rhs = self.additive()?;
+        Ok(Condition::Compare(lhs, comparison, rhs))
+    }
+
+    fn additive(&mut self) -> Result<Formula, Error> {
+        let mut formula = self.product()?;
+        loop {
+            if self.eat(&Token::Symbol("+")) {
+                formula = match formula {
+                    Formula::Add(mut formulas) => {
+                        formulas.push(self.product()?);
+                        Formula::Add(formulas)
+                    }
+                    formula => Formula::Add(vec![formula, self.product()?]),
+                };
+            } else if self.eat(&Token::Symbol("-")) {
+                formula = Formula::Sub(Box::new(formula), Box::new(self.product()?));
+            } else {
+                return Ok(formula);
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<Formula, Error> {
+        let mut formula = self.unary()?;
+        loop {
+            if self.eat(&Token::Symbol("*")) {
+                formula = match formula {
+                    Formula::Mul(mut formulas) => {
+                        formulas.push(self.unary()?);
+                        Formula::Mul(formulas)
+                    }
+                    formula => Formula::Mul(vec![formula, self.unary()?]),
+                };
+            } else if self.eat(&Token::Symbol("/")) {
+                formula = Formula::Div(Box::new(formula), Box::new(self.unary()?));
+            } else {
+                return Ok(formula);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Result<Formula, Error> {
+        let Some((token, span)) = self.tokens.get(self.position).cloned() else {
+            return Err(self.error(ErrorKind::Expected("operand")));
+        };
+        self.position += 1;
+        Ok(match token {
+            Token::Symbol("-") => match self.unary()? {
+                Formula::Constant(constant) => Formula::Constant(-constant),
+                formula => Formula::Sub(Box::new(Formula::Constant(0.0)), Box::new(formula)),
+            },
+            Token::Symbol("(") => {
+                let formula = self.formula()?;
+                self.expect(Token::Symbol(")"), ")")?;
+                formula
+            }
+            Token::Number(number) => Formula::Constant(number),
+            Token::FattyAcid(fatty_acid) => Formula::Sum(Term::Acid(fatty_acid)),
+            Token::Name(name) => Formula::Sum(term(name, self.boundaries).ok_or(Error {
+                span,
+                kind: ErrorKind::Name(name.to_owned()),
+            })?),
+            Token::Symbol(_) => {
+                self.position -= 1;
+                return Err(self.error(ErrorKind::Expected("operand")));
+            }
+        })
+    }
+
+    fn peek(&self) -> Option<&Token<'a>> {
+        self.tokens.get(self.position).map(|(token, _)| token)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        let eaten = self.peek() == Some(token);
+        if eaten {
+            self.position += 1;
+        }
+        eaten
+    }
+
+    fn expect(&mut self, token: Token, expected: &'static str) -> Result<(), Error> {
+        if !self.eat(&token) {
+            return Err(self.error(ErrorKind::Expected(expected)));
+        }
+        Ok(())
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        let span = match self.tokens.get(self.position) {
+            Some((_, span)) => span.clone(),
+            None => self.end..self.end,
+        };
+        Error { span, kind }
+    }
+}
+
+fn term(name: &str, boundaries: &Boundaries) -> Option<Term> {
+    let omega = |name: &str| name.strip_prefix('n')?.parse().ok();
+    let chain_length = |class| Term::ChainLength {
+        class,
+        boundaries: *boundaries,
+    };
+    Some(match name {
+        "SUM" => Term::Total,
+        "SFA" => Term::Sfa,
+        "UFA" => Term::Ufa,
+        "MUFA" => Term::Mufa,
+        "PUFA" => Term::Pufa,
+        "TFA" => Term::Tfa(false),
+        "TFA_CLA" => Term::Tfa(true),
+        "UI" => Term::Bonds,
+        "SCFA" => chain_length(ChainLength::Short),
+        "MCFA" => chain_length(ChainLength::Medium),
+        "LCFA" => chain_length(ChainLength::Long),
+        "VLCFA" => chain_length(ChainLength::VeryLong),
+        "OCFA" => Term::Parity(Parity::Odd),
+        "ECFA" => Term::Parity(Parity::Even),
+        "OA" => Term::Acid(fatty_acid!(18;9)),
+        "LA" => Term::Acid(fatty_acid!(18;9,12)),
+        "GLA" => Term::Acid(fatty_acid!(18;6,9,12)),
+        "ALA" => Term::Acid(fatty_acid!(18;9,12,15)),
+        "ARA" => Term::Acid(fatty_acid!(20;5,8,11,14)),
+        "EPA" => Term::Acid(fatty_acid!(20;5,8,11,14,17)),
+        "DPA" => Term::Acid(fatty_acid!(22;7,10,13,16,19)),
+        "DHA" => Term::Acid(fatty_acid!(22;4,7,10,13,16,19)),
+        _ => match name.strip_prefix("PUFA_") {
+            Some(name) => Term::PufaN(omega(name)?),
+            None => Term::N(omega(name)?),
+        },
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn boundaries() {
+        let boundaries = Boundaries {
+            medium: 8,
+            long: 14,
+            very_long: 22,
+        };
+        assert_eq!(
+            parse_with("MCFA", &boundaries),
+            Ok(Formula::Sum(Term::ChainLength {
+                class: ChainLength::Medium,
+                boundaries,
+            })),
+        );
+        assert_eq!(
+            super::parse("MCFA"),
+            Ok(Formula::Sum(Term::ChainLength {
+                class: ChainLength::Medium,
+                boundaries: Boundaries::default(),
+            })),
+        );
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text| super::parse(text).unwrap_err();
+        assert_eq!(
+            error("EPA + XYZ"),
+            Error {
+                span: 6..9,
+                kind: ErrorKind::Name("XYZ".to_owned()),
+            },
+        );
+        assert_eq!(
+            error("(EPA + DHA"),
+            Error {
+                span: 10..10,
+                kind: ErrorKind::Expected(")"),
+            },
+        );
+        assert_eq!(
+            error("C18:3n16 / SUM"),
+            Error {
+                span: 0..8,
+                kind: ErrorKind::FattyAcid(parse::ErrorKind::Omega(16)),
+            },
+        );
+        assert_eq!(error("SUM - 18:0n-9").span, 6..13);
+        assert_eq!(error("1 + * 2").span, 4..5);
+        assert_eq!(error("SUM $").kind, ErrorKind::Character('$'));
+        assert_eq!(
+            error("if SUM then 1 else 2").kind,
+            ErrorKind::Expected("comparison"),
+        );
+        assert_eq!(error("1 2").to_string(), "expected end at 2..3");
+    }
+}
@@ -14,7 +14,7 @@ pub macro fatty_acid($c:expr $(; $($i:expr),*)*) {{
             let r#i8 = ($i as i8);
             let unsaturated = Unsaturated {
                 unsaturation: Unsaturation::try_from(_count).ok(),
-                index: (r#i8 != 0).then_some(r#i8.abs() as _) ,
+                index: (r#i8 != 0).then_some(r#i8.unsigned_abs()),
                 isomerism: Isomerism::try_from(r#i8).ok(),
             };
             fatty_acid.unsaturated.push(unsaturated);
@@ -158,6 +158,93 @@ impl FattyAcid {
             }
         })
     }
+
+    /// ω (n-)
+    ///
+    /// The position of the last unsaturated bond counted from the methyl end.
+    /// `None` for saturated fatty acids and unknown positions.
+    pub fn omega(&self) -> Option<u8> {
+        let mut last = None;
+        for unsaturated in &self.unsaturated {
+            last = last.max(Some(unsaturated.index?));
+        }
+        self.carbons.checked_sub(last?)
+    }
+
+    /// Matches
+    ///
+    /// The fatty acid has the same carbons and unsaturated bonds. Unknown bond
+    /// indices and isomerism of the pattern match any, unknown ones of the
+    /// fatty acid match only those. The same as `FattyAcidExpr::matches` of
+    /// the `polars` feature, see [`positional`](Self::positional) for the
+    /// lookup that also counts fatty acids of unknown positions.
+    pub fn matches(&self, pattern: &FattyAcid) -> bool {
+        self.carbons == pattern.carbons
+            && self.unsaturated.len() == pattern.unsaturated.len()
+            && pattern.unsaturated.iter().all(|bond| {
+                let Some(index) = bond.index else {
+                    return true;
+                };
+                self.unsaturated.iter().any(|unsaturated| {
+                    unsaturated.index == Some(index)
+                        && (bond.isomerism.is_none() || unsaturated.isomerism == bond.isomerism)
+                })
+            })
+    }
+
+    /// Positional
+    ///
+    /// The fatty acid has the same carbons and number of unsaturated bonds,
+    /// and a bond at each known index of the pattern. Unlike
+    /// [`matches`](Self::matches), a bond of unknown isomerism matches any
+    /// isomerism of the pattern, and a fatty acid without known positions
+    /// matches by the number of bonds alone. The same as
+    /// `FattyAcidExpr::positional` of the `polars` feature, the lookup of the
+    /// `Find` filters.
+    pub fn positional(&self, pattern: &FattyAcid) -> bool {
+        if self.carbons != pattern.carbons || self.unsaturated.len() != pattern.unsaturated.len() {
+            return false;
+        }
+        if self.unsaturated.iter().all(|bond| bond.index.is_none()) {
+            return true;
+        }
+        pattern.unsaturated.iter().all(|bond| {
+            let Some(index) = bond.index else {
+                return true;
+            };
+            self.unsaturated.iter().any(|unsaturated| {
+                unsaturated.index == Some(index)
+                    && (unsaturated.isomerism.is_none()
+                        || bond.isomerism.is_none()
+                        || unsaturated.isomerism == bond.isomerism)
+            })
+        })
+    }
+
+    /// Trans
+    ///
+    /// At least one unsaturated bond is trans.
+    pub fn is_trans(&self) -> bool {
+        self.unsaturated
+            .iter()
+            .any(|unsaturated| unsaturated.isomerism == Some(Isomerism::Trans))
+    }
+
+    /// Conjugated
+    ///
+    /// At least two unsaturated bonds are adjacent, not interrupted by a
+    /// methylene group (e.g. CLA 18:2Δ9,11t).
+    pub fn is_conjugated(&self) -> bool {
+        let mut indices = self
+            .unsaturated
+            .iter()
+            .filter_map(|unsaturated| unsaturated.index)
+            .collect::<Vec<_>>();
+        indices.sort();
+        indices
+            .windows(2)
+            .any(|indices| indices[1] - indices[0] == 2)
+    }
 }
 
 /// Unsaturated
@@ -208,6 +295,15 @@ pub enum Unsaturation {
     Two = 2,
 }
 
+impl From<Unsaturation> for u8 {
+    fn from(value: Unsaturation) -> Self {
+        match value {
+            Unsaturation::One => 1,
+            Unsaturation::Two => 2,
+        }
+    }
+}
+
 impl TryFrom<u8> for Unsaturation {
     type Error = u8;
 
@@ -220,13 +316,33 @@ impl TryFrom<u8> for Unsaturation {
     }
 }
 
+/// Canonical order of the unsaturated bonds, by index, isomerism and
+/// unsaturation
+pub(crate) fn sort(unsaturated: &mut [Unsaturated]) {
+    unsaturated.sort_by_cached_key(|unsaturated| {
+        (
+            unsaturated.index,
+            unsaturated.isomerism,
+            unsaturated.unsaturation,
+        )
+    });
+}
+
+pub mod chain_length;
+pub mod composition;
+pub mod derivative;
 pub mod display;
+pub mod ecl;
+pub mod identification;
+pub mod index;
+pub mod parse;
+#[cfg(feature = "polars")]
 pub mod polars;
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::fatty_acid::display::{COMMON, DisplayWithOptions};
+    use crate::fatty_acid::display::{COMMON, DisplayWithOptions, ID, Options};
 
     #[test]
     fn test() {
@@ -236,6 +352,69 @@ mod test {
         assert_eq!(format!("{fatty_acid:#}"), "18:1Δ9");
         assert_eq!(format!("{fatty_acid:#02}"), "18:01Δ09");
     }
+
+    #[test]
+    fn isomerism() {
+        let fatty_acid = fatty_acid!(18;-9,-12,-15).display(COMMON);
+        assert_eq!(fatty_acid.to_string(), "18:3");
+        assert_eq!(format!("{fatty_acid:#}"), "18:3Δ9t,12t,15t");
+        assert_eq!(format!("{fatty_acid:#02}"), "18:03Δ09t,12t,15t");
+        let fatty_acid = fatty_acid!(18;9,12).display(ID);
+        assert_eq!(fatty_acid.to_string(), "c18u2");
+        assert_eq!(format!("{fatty_acid:#}"), "c18u2c9c12");
+        assert_eq!(format!("{fatty_acid:#02}"), "c18u02c09c12");
+    }
+
+    #[test]
+    fn omega() {
+        assert_eq!(fatty_acid!(18).omega(), None);
+        assert_eq!(fatty_acid!(18;9).omega(), Some(9));
+        assert_eq!(fatty_acid!(18;12,9).omega(), Some(6));
+        assert_eq!(fatty_acid!(22;4,7,10,13,16,19).omega(), Some(3));
+        let mut fatty_acid = fatty_acid!(18;9,12);
+        fatty_acid.unsaturated[0].index = None;
+        assert_eq!(fatty_acid.omega(), None);
+    }
+
+    #[test]
+    fn positional() {
+        let pattern = fatty_acid!(18;9,12);
+        let mut unknown = fatty_acid!(18;9,12);
+        for bond in &mut unknown.unsaturated {
+            bond.isomerism = None;
+        }
+        let mut without = unknown.clone();
+        for bond in &mut without.unsaturated {
+            bond.index = None;
+        }
+        for (fatty_acid, positional, matches) in [
+            (fatty_acid!(18;12,9), true, true),
+            (unknown, true, false),
+            (without, true, false),
+            (fatty_acid!(18;-9,-12), false, false),
+            (fatty_acid!(18;9,15), false, false),
+            (fatty_acid!(18;9), false, false),
+        ] {
+            assert_eq!(
+                fatty_acid.positional(&pattern),
+                positional,
+                "{fatty_acid:?}"
+            );
+            assert_eq!(fatty_acid.matches(&pattern), matches, "{fatty_acid:?}");
+        }
+    }
+
+    #[test]
+    fn options() {
+        let options = Options {
+            alternate: true,
+            width: Some(2),
+            ..COMMON
+        };
+        let fatty_acid = fatty_acid!(18;9).display(options);
+        assert_eq!(fatty_acid.to_string(), "18:01Δ09");
+        assert_eq!(format!("{fatty_acid:03}"), "018:001Δ009");
+    }
 }
 
 // #[cfg(test)]
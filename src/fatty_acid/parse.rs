@@ -0,0 +1,461 @@
+use crate::fatty_acid::{FattyAcid, Isomerism, Unsaturated, Unsaturation};
+use serde::{Deserialize, Serialize};
+use std::{
+    error,
+    fmt::{self, Formatter},
+    str::FromStr,
+};
+
+impl FromStr for FattyAcid {
+    type Err = Error;
+
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse(text, Format::Auto)
+    }
+}
+
+/// Parse a fatty acid
+///
+/// Leading and trailing whitespace is ignored.
+pub fn parse(text: &str, format: Format) -> Result<FattyAcid, Error> {
+    let offset = text.len() - text.trim_start().len();
+    let text = text.trim();
+    let parse = |format| {
+        let mut parser = Parser { text, offset: 0 };
+        let fatty_acid = match format {
+            Format::Common => parser.common()?,
+            Format::Id => parser.id()?,
+            Format::Auto => unreachable!(),
+        };
+        parser.end()?;
+        Ok(fatty_acid)
+    };
+    match format {
+        Format::Auto => parse(Format::Id).or_else(|id: Error| {
+            parse(Format::Common).map_err(|common| {
+                if id.offset > common.offset {
+                    id
+                } else {
+                    common
+                }
+            })
+        }),
+        format => parse(format),
+    }
+    .map_err(|error| Error {
+        offset: error.offset + offset,
+        ..error
+    })
+}
+
+/// Parse format
+#[derive(
+    Clone, Copy, Debug, Default, Deserialize, Eq, Hash, Ord, PartialEq, PartialOrd, Serialize,
+)]
+pub enum Format {
+    /// Any of the formats below
+    #[default]
+    Auto,
+    /// `18:1Δ9`, `C18:2Δ9,12t`, `18:2n-6`, `C20:5ω3`
+    ///
+    /// Bonds without isomerism are cis. The ω (n-) form assumes all cis,
+    /// methylene interrupted bonds.
+    Common,
+    /// `c18u1c9`, `c18u2c9t12`
+    Id,
+}
+
+/// Parse error
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Error {
+    /// Byte offset into the parsed text
+    pub offset: usize,
+    pub kind: ErrorKind,
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} at {}", self.kind, self.offset)
+    }
+}
+
+impl error::Error for Error {}
+
+/// Parse error kind
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum ErrorKind {
+    /// Expected token
+    Expected(&'static str),
+    /// Number does not fit
+    Overflow,
+    /// Zero carbons
+    Carbons,
+    /// Bond index out of range
+    Index(u8),
+    /// Number of bond indices does not match number of unsaturated bonds
+    Indices { expected: u8, actual: usize },
+    /// More unsaturated bonds than bonds
+    Unsaturated(u8),
+    /// ω position out of range
+    Omega(u8),
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::Expected(expected) => write!(f, "expected {expected}"),
+            Self::Overflow => f.write_str("number too large"),
+            Self::Carbons => f.write_str("zero carbons"),
+            Self::Index(index) => write!(f, "bond index {index} out of range"),
+            Self::Indices { expected, actual } => {
+                write!(f, "expected {expected} bond indices, found {actual}")
+            }
+            Self::Unsaturated(unsaturated) => {
+                write!(f, "too many unsaturated bonds {unsaturated}")
+            }
+            Self::Omega(omega) => write!(f, "ω position {omega} out of range"),
+        }
+    }
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    offset: usize,
+}
+
+impl Parser<'_> {
+    /// `[C]C:U[ΔI[,I]*|(n|ω|w)[-]N]`
+    fn common(&mut self) -> Result<FattyAcid, Error> {
+        self.eat("C");
+        let carbons = self.carbons()?;
+        self.expect(":")?;
+        let unsaturated = self.unsaturated(carbons)?;
+        let mut fatty_acid = FattyAcid::new(carbons);
+        if self.eat("Δ") {
+            let offset = self.offset;
+            loop {
+                let index = self.index(carbons)?;
+                // Cis is implied, see `Elision::Implicit`
+                let isomerism = if self.eat("t") || self.eat("E") {
+                    Isomerism::Trans
+                } else {
+                    if !self.eat("c") {
+                        self.eat("Z");
+                    }
+                    Isomerism::Cis
+                };
+                fatty_acid.unsaturated.push(Unsaturated {
+                    index: Some(index),
+                    isomerism: Some(isomerism),
+                    unsaturation: Some(Unsaturation::One),
+                });
+                if !self.eat(",") {
+                    break;
+                }
+            }
+            if fatty_acid.unsaturated.len() != unsaturated as usize {
+                return Err(Error {
+                    offset,
+                    kind: ErrorKind::Indices {
+                        expected: unsaturated,
+                        actual: fatty_acid.unsaturated.len(),
+                    },
+                });
+            }
+        } else if let Some(omega) = self.omega()? {
+            let offset = self.offset;
+            let error = Error {
+                offset,
+                kind: ErrorKind::Omega(omega),
+            };
+            // A saturated fatty acid has no ω position
+            if unsaturated == 0 {
+                return Err(error);
+            }
+            // Methylene interrupted: the last bond is `n` carbons from the
+            // methyl end and every other bond is 3 carbons before it.
+            let last = carbons.checked_sub(omega).ok_or(error.clone())?;
+            let first = last
+                .checked_sub(3 * unsaturated.saturating_sub(1))
+                .filter(|&first| first > 0 && last < carbons)
+                .ok_or(error)?;
+            for index in (first..=last).step_by(3) {
+                fatty_acid.unsaturated.push(Unsaturated {
+                    index: Some(index),
+                    isomerism: Some(Isomerism::Cis),
+                    unsaturation: Some(Unsaturation::One),
+                });
+            }
+        } else {
+            for _ in 0..unsaturated {
+                fatty_acid.unsaturated.push(Unsaturated {
+                    index: None,
+                    isomerism: None,
+                    unsaturation: Some(Unsaturation::One),
+                });
+            }
+        }
+        Ok(fatty_acid)
+    }
+
+    /// `cCuU[(c|t)I]*`
+    fn id(&mut self) -> Result<FattyAcid, Error> {
+        self.expect("c")?;
+        let carbons = self.carbons()?;
+        self.expect("u")?;
+        let unsaturated = self.unsaturated(carbons)?;
+        let mut fatty_acid = FattyAcid::new(carbons);
+        let offset = self.offset;
+        loop {
+            let isomerism = if self.eat("c") {
+                Isomerism::Cis
+            } else if self.eat("t") {
+                Isomerism::Trans
+            } else {
+                break;
+            };
+            fatty_acid.unsaturated.push(Unsaturated {
+                index: Some(self.index(carbons)?),
+                isomerism: Some(isomerism),
+                unsaturation: Some(Unsaturation::One),
+            });
+        }
+        match fatty_acid.unsaturated.len() {
+            0 => {
+                for _ in 0..unsaturated {
+                    fatty_acid.unsaturated.push(Unsaturated {
+                        index: None,
+                        isomerism: None,
+                        unsaturation: Some(Unsaturation::One),
+                    });
+                }
+            }
+            actual if actual != unsaturated as usize => {
+                return Err(Error {
+                    offset,
+                    kind: ErrorKind::Indices {
+                        expected: unsaturated,
+                        actual,
+                    },
+                });
+            }
+            _ => {}
+        }
+        Ok(fatty_acid)
+    }
+
+    /// `[ ][(](n|ω|w)[-]N[)]`
+    fn omega(&mut self) -> Result<Option<u8>, Error> {
+        let offset = self.offset;
+        self.eat(" ");
+        let parenthesis = self.eat("(");
+        if !(self.eat("n") || self.eat("ω") || self.eat("w")) {
+            self.offset = offset;
+            return Ok(None);
+        }
+        self.eat("-");
+        let omega = self.number()?;
+        if parenthesis {
+            self.expect(")")?;
+        }
+        Ok(Some(omega))
+    }
+
+    fn carbons(&mut self) -> Result<u8, Error> {
+        let offset = self.offset;
+        match self.number()? {
+            0 => Err(Error {
+                offset,
+                kind: ErrorKind::Carbons,
+            }),
+            carbons => Ok(carbons),
+        }
+    }
+
+    fn unsaturated(&mut self, carbons: u8) -> Result<u8, Error> {
+        let offset = self.offset;
+        let unsaturated = self.number()?;
+        if unsaturated >= carbons {
+            return Err(Error {
+                offset,
+                kind: ErrorKind::Unsaturated(unsaturated),
+            });
+        }
+        Ok(unsaturated)
+    }
+
+    fn index(&mut self, carbons: u8) -> Result<u8, Error> {
+        let offset = self.offset;
+        let index = self.number()?;
+        if index == 0 || index >= carbons {
+            return Err(Error {
+                offset,
+                kind: ErrorKind::Index(index),
+            });
+        }
+        Ok(index)
+    }
+
+    fn number(&mut self) -> Result<u8, Error> {
+        let rest = &self.text[self.offset..];
+        let length = rest
+            .find(|char: char| !char.is_ascii_digit())
+            .unwrap_or(rest.len());
+        if length == 0 {
+            return Err(self.error(ErrorKind::Expected("number")));
+        }
+        let number = rest[..length]
+            .parse()
+            .map_err(|_| self.error(ErrorKind::Overflow))?;
+        self.offset += length;
+        Ok(number)
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        let eaten = self.text[self.offset..].starts_with(token);
+        if eaten {
+            self.offset += token.len();
+        }
+        eaten
+    }
+
+    fn expect(&mut self, token: &'static str) -> Result<(), Error> {
+        if !self.eat(token) {
+            return Err(self.error(ErrorKind::Expected(token)));
+        }
+        Ok(())
+    }
+
+    fn end(&self) -> Result<(), Error> {
+        if self.offset != self.text.len() {
+            return Err(self.error(ErrorKind::Expected("end")));
+        }
+        Ok(())
+    }
+
+    fn error(&self, kind: ErrorKind) -> Error {
+        Error {
+            offset: self.offset,
+            kind,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        display::{COMMON, DisplayWithOptions as _, ID, Options},
+        fatty_acid,
+    };
+
+    #[test]
+    fn common() {
+        assert_eq!(parse("18:0", Format::Common), Ok(fatty_acid!(18)));
+        assert_eq!(parse("C18:1Δ9", Format::Common), Ok(fatty_acid!(18;9)));
+        assert_eq!(
+            parse("18:2Δ9c,12t", Format::Common),
+            Ok(fatty_acid!(18;9,-12)),
+        );
+        assert_eq!(
+            parse("18:2Δ9Z,12", Format::Common),
+            Ok(fatty_acid!(18;9,12))
+        );
+        assert_eq!(
+            parse("18:2", Format::Common).map(|fatty_acid| fatty_acid.unsaturated.len()),
+            Ok(2),
+        );
+    }
+
+    #[test]
+    fn omega() {
+        assert_eq!(parse("C18:2n6", Format::Common), Ok(fatty_acid!(18;9,12)));
+        assert_eq!(parse("18:1n-9", Format::Common), Ok(fatty_acid!(18;9)));
+        assert_eq!(
+            parse("18:3 (n-3)", Format::Common),
+            Ok(fatty_acid!(18;9,12,15)),
+        );
+        assert_eq!(
+            parse("20:5ω3", Format::Common),
+            Ok(fatty_acid!(20;5,8,11,14,17)),
+        );
+        assert_eq!(
+            parse("22:6w-3", Format::Common),
+            Ok(fatty_acid!(22;4,7,10,13,16,19)),
+        );
+        assert_eq!(
+            parse("18:3n16", Format::Common).map_err(|error| error.kind),
+            Err(ErrorKind::Omega(16)),
+        );
+        assert_eq!(
+            parse("18:0n-9", Format::Common).map_err(|error| error.kind),
+            Err(ErrorKind::Omega(9)),
+        );
+    }
+
+    #[test]
+    fn id() {
+        assert_eq!(parse("c18u0", Format::Id), Ok(fatty_acid!(18)));
+        assert_eq!(parse("c18u1c9", Format::Id), Ok(fatty_acid!(18;9)));
+        assert_eq!(parse("c18u2c9t12", Format::Id), Ok(fatty_acid!(18;9,-12)));
+        assert_eq!(
+            parse("c18u2c9", Format::Id).map_err(|error| error.kind),
+            Err(ErrorKind::Indices {
+                expected: 2,
+                actual: 1,
+            }),
+        );
+    }
+
+    #[test]
+    fn auto() {
+        for text in ["18:1Δ9", "C18:1n9", "c18u1c9", " 18:1Δ9 "] {
+            assert_eq!(text.parse(), Ok(fatty_acid!(18;9)), "{text}");
+        }
+        for fatty_acid in [
+            fatty_acid!(16),
+            fatty_acid!(18;9,-12),
+            fatty_acid!(20;5,8,11,14,17),
+        ] {
+            for options in [COMMON, ID] {
+                let text = (&fatty_acid)
+                    .display(Options {
+                        alternate: true,
+                        ..options
+                    })
+                    .to_string();
+                assert_eq!(text.parse(), Ok(fatty_acid.clone()), "{text}");
+            }
+        }
+    }
+
+    #[test]
+    fn errors() {
+        let error = |text: &str| text.parse::<FattyAcid>().unwrap_err();
+        assert_eq!(
+            error(""),
+            Error {
+                offset: 0,
+                kind: ErrorKind::Expected("number"),
+            },
+        );
+        assert_eq!(
+            error("18:1Δ9x"),
+            Error {
+                offset: 7,
+                kind: ErrorKind::Expected("end"),
+            },
+        );
+        assert_eq!(error("0:0").kind, ErrorKind::Carbons);
+        assert_eq!(error("300:0").kind, ErrorKind::Overflow);
+        assert_eq!(error("18:1Δ18").kind, ErrorKind::Index(18));
+        assert_eq!(error("4:4").kind, ErrorKind::Unsaturated(4));
+        assert_eq!(
+            error("  18:x"),
+            Error {
+                offset: 5,
+                kind: ErrorKind::Expected("number"),
+            },
+        );
+    }
+}
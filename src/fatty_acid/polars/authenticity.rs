@@ -0,0 +1,356 @@
+use super::DataFrameExt as _;
+use crate::fatty_acid::composition::Composition;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Distance metric
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Metric {
+    #[default]
+    Euclidean,
+    /// `1 - cos θ`
+    Cosine,
+    /// `Σ|x - y| / Σ(x + y)`
+    BrayCurtis,
+}
+
+/// Binary blend estimate
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Blend {
+    /// Fraction of the second oil, `0..=1`
+    pub ratio: f64,
+    /// Euclidean distance between the sample and the estimated blend
+    pub residual: f64,
+}
+
+/// Distance between two compositions
+///
+/// Both data frames have the fatty acid [`COLUMN`](super::COLUMN) and the `value` column.
+/// Compositions are aligned by fatty acid, an absent fatty acid is 0, and
+/// normalized to 100, a composition summing to 0 is an error. Fatty acids are
+/// compared by their [`Composition`] keys, so `18:2Δ12,9` and `18:2Δ9,12` are
+/// the same, but `18:1` without a position and `18:1Δ9` are different.
+pub fn distance(
+    sample: &DataFrame,
+    reference: &DataFrame,
+    value: &str,
+    metric: Metric,
+) -> PolarsResult<f64> {
+    let aligned = align(&[sample, reference], value)?;
+    Ok(metric.distance(&aligned[0], &aligned[1]))
+}
+
+/// References ranked by distance to the sample, the closest first, see
+/// [`distance`]
+pub fn rank<'a>(
+    sample: &DataFrame,
+    references: impl IntoIterator<Item = (&'a str, &'a DataFrame)>,
+    value: &str,
+    metric: Metric,
+) -> PolarsResult<Vec<(&'a str, f64)>> {
+    let mut ranked = references
+        .into_iter()
+        .map(|(name, reference)| Ok((name, distance(sample, reference, value, metric)?)))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    ranked.sort_by(|left, right| left.1.total_cmp(&right.1));
+    Ok(ranked)
+}
+
+/// Mahalanobis distance between the sample and replicates of a reference
+///
+/// `d = √((x - μ)ᵀ (S + λI)⁻¹ (x - μ))`, where `μ` and `S` are the mean and
+/// the covariance of the replicates. Normalized compositions always have a
+/// singular covariance, so the ridge `λ` must be positive.
+pub fn mahalanobis(
+    sample: &DataFrame,
+    replicates: &[DataFrame],
+    value: &str,
+    ridge: f64,
+) -> PolarsResult<f64> {
+    polars_ensure!(replicates.len() > 1, ComputeError: "at least two replicates are required");
+    polars_ensure!(ridge > 0.0, ComputeError: "ridge must be positive");
+    let data_frames = std::iter::once(sample)
+        .chain(replicates)
+        .collect::<Vec<_>>();
+    let mut compositions = align(&data_frames, value)?;
+    let replicates = compositions.split_off(1);
+    let sample = &compositions[0];
+    let (count, length) = (replicates.len() as f64, sample.len());
+    let mean = (0..length)
+        .map(|index| {
+            replicates
+                .iter()
+                .map(|replicate| replicate[index])
+                .sum::<f64>()
+                / count
+        })
+        .collect::<Vec<_>>();
+    let mut covariance = vec![vec![0.0; length]; length];
+    for (row, covariances) in covariance.iter_mut().enumerate() {
+        for (column, covariance) in covariances.iter_mut().enumerate() {
+            *covariance = replicates
+                .iter()
+                .map(|replicate| (replicate[row] - mean[row]) * (replicate[column] - mean[column]))
+                .sum::<f64>()
+                / (count - 1.0);
+        }
+        covariances[row] += ridge;
+    }
+    let difference = sample
+        .iter()
+        .zip(&mean)
+        .map(|(sample, mean)| sample - mean)
+        .collect::<Vec<_>>();
+    let solution = cholesky_solve(covariance, &difference)
+        .ok_or_else(|| polars_err!(ComputeError: "covariance is not positive definite"))?;
+    Ok(dot(&difference, &solution).sqrt())
+}
+
+/// Binary blend of the first and the second oil closest to the sample
+///
+/// Least squares `x ≈ (1 - r)a + rb` constrained to `0 ≤ r ≤ 1`, on
+/// compositions aligned and normalized as in [`distance`].
+pub fn blend(
+    sample: &DataFrame,
+    first: &DataFrame,
+    second: &DataFrame,
+    value: &str,
+) -> PolarsResult<Blend> {
+    let aligned = align(&[sample, first, second], value)?;
+    let (sample, first, second) = (&aligned[0], &aligned[1], &aligned[2]);
+    let direction = sub(second, first);
+    let norm = dot(&direction, &direction);
+    let ratio = if norm > 0.0 {
+        (dot(&sub(sample, first), &direction) / norm).clamp(0.0, 1.0)
+    } else {
+        0.0
+    };
+    let blended = first
+        .iter()
+        .zip(&direction)
+        .map(|(first, direction)| first + ratio * direction)
+        .collect::<Vec<_>>();
+    Ok(Blend {
+        ratio,
+        residual: Metric::Euclidean.distance(sample, &blended),
+    })
+}
+
+impl Metric {
+    fn distance(&self, left: &[f64], right: &[f64]) -> f64 {
+        match self {
+            Self::Euclidean => {
+                let difference = sub(left, right);
+                dot(&difference, &difference).sqrt()
+            }
+            Self::Cosine => {
+                1.0 - dot(left, right) / (dot(left, left).sqrt() * dot(right, right).sqrt())
+            }
+            Self::BrayCurtis => {
+                let difference = left
+                    .iter()
+                    .zip(right)
+                    .map(|(left, right)| (left - right).abs())
+                    .sum::<f64>();
+                difference / (left.iter().sum::<f64>() + right.iter().sum::<f64>())
+            }
+        }
+    }
+}
+
+/// Compositions aligned by fatty acid and normalized to 100
+///
+/// A composition summing to 0 can not be normalized and is an error.
+pub(super) fn align(data_frames: &[&DataFrame], value: &str) -> PolarsResult<Vec<Vec<f64>>> {
+    let compositions = data_frames
+        .iter()
+        .map(|data_frame| composition(data_frame, value))
+        .collect::<PolarsResult<Vec<_>>>()?;
+    // The fatty acids of all compositions, in order of appearance
+    let mut fatty_acids = Composition::default();
+    for composition in &compositions {
+        for (fatty_acid, _) in composition.iter() {
+            if fatty_acids.get(fatty_acid).is_none() {
+                fatty_acids.insert(fatty_acid.clone(), 0.0);
+            }
+        }
+    }
+    let aligned = compositions.into_iter().map(|composition| {
+        polars_ensure!(
+            composition.total() != 0.0,
+            ComputeError: "composition `{value}` sums to 0"
+        );
+        let composition = composition.normalized();
+        Ok(fatty_acids
+            .iter()
+            .map(|(fatty_acid, _)| composition.get(fatty_acid).unwrap_or_default())
+            .collect())
+    });
+    aligned.collect()
+}
+
+/// Composition of the fatty acid [`COLUMN`](super::COLUMN) and the `value`
+/// column
+///
+/// Null fatty acids and values are skipped, values of the same fatty acid are
+/// summed.
+pub(super) fn composition(data_frame: &DataFrame, value: &str) -> PolarsResult<Composition> {
+    let fatty_acids = data_frame.try_fatty_acid()?.to_vec()?;
+    let values = data_frame[value].cast(&DataType::Float64)?;
+    let mut composition = Composition::default();
+    for (fatty_acid, value) in fatty_acids.into_iter().zip(values.f64()?) {
+        if let (Some(fatty_acid), Some(value)) = (fatty_acid, value) {
+            let sum = composition.get(&fatty_acid).unwrap_or_default() + value;
+            composition.insert(fatty_acid, sum);
+        }
+    }
+    Ok(composition)
+}
+
+fn dot(left: &[f64], right: &[f64]) -> f64 {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left * right)
+        .sum()
+}
+
+fn sub(left: &[f64], right: &[f64]) -> Vec<f64> {
+    left.iter()
+        .zip(right)
+        .map(|(left, right)| left - right)
+        .collect()
+}
+
+/// Solve `Ax = b` for a symmetric positive definite `A`
+fn cholesky_solve(mut a: Vec<Vec<f64>>, b: &[f64]) -> Option<Vec<f64>> {
+    let length = b.len();
+    // Lower triangle `L`, `A = LLᵀ`
+    for column in 0..length {
+        let diagonal = a[column][column] - (0..column).map(|k| a[column][k].powi(2)).sum::<f64>();
+        if diagonal <= 0.0 {
+            return None;
+        }
+        a[column][column] = diagonal.sqrt();
+        for row in column + 1..length {
+            let sum = (0..column).map(|k| a[row][k] * a[column][k]).sum::<f64>();
+            a[row][column] = (a[row][column] - sum) / a[column][column];
+        }
+    }
+    // `Ly = b`
+    let mut x = b.to_vec();
+    for row in 0..length {
+        x[row] = (x[row] - (0..row).map(|k| a[row][k] * x[k]).sum::<f64>()) / a[row][row];
+    }
+    // `Lᵀx = y`
+    for row in (0..length).rev() {
+        x[row] = (x[row] - (row + 1..length).map(|k| a[k][row] * x[k]).sum::<f64>()) / a[row][row];
+    }
+    Some(x)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{fatty_acid, polars::COLUMN};
+
+    fn composition(values: [f64; 3]) -> DataFrame {
+        df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(16), fatty_acid!(18;9), fatty_acid!(18;9,12)],
+            ),
+            "Value" => values,
+        }
+        .unwrap()
+    }
+
+    #[test]
+    fn distance() -> PolarsResult<()> {
+        let left = composition([20.0, 80.0, 0.0]);
+        let right = composition([20.0, 0.0, 80.0]);
+        let distance = |metric| super::distance(&left, &right, "Value", metric);
+        assert!((distance(Metric::Euclidean)? - 80.0 * 2f64.sqrt()).abs() < 1e-9);
+        // cos θ = 400 / 6800
+        assert!((distance(Metric::Cosine)? - (1.0 - 400.0 / 6800.0)).abs() < 1e-9);
+        assert!((distance(Metric::BrayCurtis)? - 0.8).abs() < 1e-9);
+        // Normalized and aligned
+        let scaled = df! {
+            COLUMN => Series::new(PlSmallStr::EMPTY, vec![fatty_acid!(18;9), fatty_acid!(16)]),
+            "Value" => [8.0, 2.0],
+        }?;
+        assert!(super::distance(&left, &scaled, "Value", Metric::Euclidean)? < 1e-9);
+        // Bonds in another order are the same fatty acid
+        let unsorted = df! {
+            COLUMN => Series::new(PlSmallStr::EMPTY, vec![fatty_acid!(18;12,9), fatty_acid!(16)]),
+            "Value" => [8.0, 2.0],
+        }?;
+        assert!(super::distance(&right, &unsorted, "Value", Metric::Euclidean)? < 1e-9);
+        // Not normalizable
+        let zero = composition([0.0; 3]);
+        let empty = composition([20.0, 80.0, 0.0]).clear();
+        for metric in [Metric::Euclidean, Metric::Cosine, Metric::BrayCurtis] {
+            assert!(super::distance(&left, &zero, "Value", metric).is_err());
+            assert!(super::distance(&empty, &left, "Value", metric).is_err());
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn rank() -> PolarsResult<()> {
+        let olive = composition([12.0, 75.0, 13.0]);
+        let sunflower = composition([6.0, 25.0, 69.0]);
+        let sample = composition([11.0, 70.0, 19.0]);
+        let ranked = super::rank(
+            &sample,
+            [("Olive", &olive), ("Sunflower", &sunflower)],
+            "Value",
+            Metric::BrayCurtis,
+        )?;
+        assert_eq!(ranked[0].0, "Olive");
+        assert!(ranked[0].1 < ranked[1].1);
+        Ok(())
+    }
+
+    #[test]
+    fn blend() -> PolarsResult<()> {
+        let olive = composition([12.0, 75.0, 13.0]);
+        let sunflower = composition([6.0, 25.0, 69.0]);
+        // 85 % olive, 15 % sunflower
+        let sample = composition([11.1, 67.5, 21.4]);
+        let blend = super::blend(&sample, &olive, &sunflower, "Value")?;
+        assert!((blend.ratio - 0.15).abs() < 1e-9);
+        assert!(blend.residual < 1e-9);
+        // Clamped
+        let blend = super::blend(&olive, &sunflower, &composition([0.0, 0.0, 100.0]), "Value")?;
+        assert_eq!(blend.ratio, 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn mahalanobis() -> PolarsResult<()> {
+        let replicates = [
+            composition([10.0, 70.0, 20.0]),
+            composition([12.0, 70.0, 18.0]),
+            composition([10.0, 72.0, 18.0]),
+            composition([12.0, 72.0, 16.0]),
+        ];
+        let mean = composition([11.0, 71.0, 18.0]);
+        assert!(super::mahalanobis(&mean, &replicates, "Value", 1e-6)? < 1e-6);
+        let near =
+            super::mahalanobis(&composition([12.0, 70.0, 18.0]), &replicates, "Value", 1e-6)?;
+        let far = super::mahalanobis(&composition([20.0, 62.0, 18.0]), &replicates, "Value", 1e-6)?;
+        assert!(near < far);
+        assert!(super::mahalanobis(&mean, &replicates[..1], "Value", 1e-6).is_err());
+        assert!(super::mahalanobis(&mean, &replicates, "Value", 0.0).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn cholesky() {
+        let a = vec![vec![4.0, 2.0], vec![2.0, 3.0]];
+        let x = cholesky_solve(a, &[2.0, 5.0]).unwrap();
+        assert!((x[0] + 0.5).abs() < 1e-12);
+        assert!((x[1] - 2.0).abs() < 1e-12);
+        assert!(cholesky_solve(vec![vec![0.0]], &[1.0]).is_none());
+    }
+}
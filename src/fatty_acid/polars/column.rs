@@ -3,11 +3,21 @@ use polars::prelude::*;
 
 /// Extension methods for [`Column`]
 pub trait ColumnExt {
+    /// # Panics
+    ///
+    /// Panics if the column is not a fatty acid column, see
+    /// [`try_fatty_acid`](Self::try_fatty_acid).
     fn fatty_acid(&self) -> FattyAcidSeries;
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries>;
 }
 
 impl ColumnExt for Column {
     fn fatty_acid(&self) -> FattyAcidSeries {
         self.as_materialized_series().fatty_acid()
     }
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries> {
+        self.as_materialized_series().try_fatty_acid()
+    }
 }
@@ -0,0 +1,337 @@
+use super::{COLUMN, DataFrameExt as _, ExprExt as _};
+use crate::fatty_acid::{
+    FattyAcid,
+    display::{COMMON, DisplayWithOptions as _, Options},
+    parse::{self, Format},
+};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+use std::fmt::{self, Formatter};
+
+/// Composition standards
+///
+/// Loaded from RON, see [`Standards::from_ron`], e.g. the Codex CXS 210
+/// named vegetable oils or national standards.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+#[serde(transparent)]
+pub struct Standards {
+    pub standards: Vec<Standard>,
+}
+
+impl Standards {
+    pub fn from_ron(text: &str) -> ron::error::SpannedResult<Self> {
+        ron::Options::default()
+            .with_default_extension(ron::extensions::Extensions::IMPLICIT_SOME)
+            .from_str(text)
+    }
+
+    pub fn to_ron(&self) -> ron::Result<String> {
+        ron::ser::to_string_pretty(
+            self,
+            ron::ser::PrettyConfig::default()
+                .extensions(ron::extensions::Extensions::IMPLICIT_SOME),
+        )
+    }
+
+    /// Standard by name, case insensitive
+    pub fn get(&self, name: &str) -> Option<&Standard> {
+        self.standards
+            .iter()
+            .find(|standard| standard.name.eq_ignore_ascii_case(name))
+    }
+}
+
+/// Composition standard
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Standard {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Publication the ranges come from
+    #[serde(default)]
+    pub source: Option<String>,
+    pub fatty_acids: Vec<Limits>,
+}
+
+/// Fatty acid limits, % of total fatty acids
+///
+/// Written in RON as a `("18:1", 55.0, 83.0)` tuple of the common notation,
+/// the minimum and the maximum. A fatty acid without positions covers all
+/// positional isomers. A not detected (ND) minimum is 0.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+#[serde(try_from = "(String, f64, f64)", into = "(String, f64, f64)")]
+pub struct Limits {
+    pub fatty_acid: FattyAcid,
+    pub min: f64,
+    pub max: f64,
+}
+
+impl TryFrom<(String, f64, f64)> for Limits {
+    type Error = parse::Error;
+
+    fn try_from((fatty_acid, min, max): (String, f64, f64)) -> Result<Self, Self::Error> {
+        Ok(Self {
+            fatty_acid: parse::parse(&fatty_acid, Format::Common)?,
+            min,
+            max,
+        })
+    }
+}
+
+impl From<Limits> for (String, f64, f64) {
+    fn from(limits: Limits) -> Self {
+        // Positions only if all are known
+        let options = Options {
+            alternate: limits
+                .fatty_acid
+                .unsaturated
+                .iter()
+                .all(|unsaturated| unsaturated.index.is_some()),
+            ..COMMON
+        };
+        (
+            limits.fatty_acid.display(options).to_string(),
+            limits.min,
+            limits.max,
+        )
+    }
+}
+
+impl Standard {
+    /// Check the composition against the standard
+    ///
+    /// The data frame has the fatty acid [`COLUMN`] and the `value` column, %
+    /// of total fatty acids. The value of each limited fatty acid is the sum
+    /// of the matching rows, see
+    /// [`FattyAcidExpr::matches`](super::FattyAcidExpr::matches).
+    pub fn check(&self, data_frame: &DataFrame, value: &str) -> PolarsResult<Compliance> {
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let mut checks = Vec::with_capacity(self.fatty_acids.len());
+        let mut listed = lit(false);
+        for limits in &self.fatty_acids {
+            let matches = fatty_acid.clone().matches(&limits.fatty_acid);
+            listed = listed.or(matches.clone());
+            let sum = data_frame
+                .clone()
+                .lazy()
+                .filter(matches.and(col(value).is_not_null()))
+                .select([
+                    col(value).cast(DataType::Float64).sum(),
+                    len().alias("Count"),
+                ])
+                .collect()?;
+            let value = (sum["Count"].u32()?.get(0) != Some(0))
+                .then(|| {
+                    sum[value]
+                        .f64()
+                        .map(|value| value.get(0).unwrap_or_default())
+                })
+                .transpose()?;
+            let status = match value {
+                None => Status::Missing,
+                Some(value) if value < limits.min => Status::Below,
+                Some(value) if value > limits.max => Status::Above,
+                Some(_) => Status::InRange,
+            };
+            checks.push(Check {
+                limits: limits.clone(),
+                value,
+                status,
+            });
+        }
+        let unlisted = data_frame
+            .clone()
+            .lazy()
+            .filter(listed.not())
+            .select([col(COLUMN), col(value).cast(DataType::Float64)])
+            .collect()?;
+        let unlisted = unlisted
+            .fatty_acid()
+            .to_vec()?
+            .into_iter()
+            .zip(unlisted[value].f64()?)
+            .filter_map(|(fatty_acid, value)| Some((fatty_acid?, value?)))
+            .collect();
+        Ok(Compliance {
+            standard: self.name.clone(),
+            checks,
+            unlisted,
+        })
+    }
+}
+
+/// Compliance report
+#[derive(Clone, Debug, PartialEq)]
+pub struct Compliance {
+    /// Standard name
+    pub standard: String,
+    pub checks: Vec<Check>,
+    /// Fatty acids without limits in the standard and their values
+    pub unlisted: Vec<(FattyAcid, f64)>,
+}
+
+impl Compliance {
+    /// All limits are met
+    ///
+    /// A missing fatty acid passes only if it may be not detected, i.e. its
+    /// minimum is 0.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(Check::passed)
+    }
+
+    /// Checks with the status
+    pub fn filter(&self, status: Status) -> impl Iterator<Item = &Check> {
+        self.checks
+            .iter()
+            .filter(move |check| check.status == status)
+    }
+}
+
+impl fmt::Display for Compliance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let verdict = if self.passed() { "passed" } else { "failed" };
+        writeln!(f, "{}: {verdict}", self.standard)?;
+        for check in &self.checks {
+            writeln!(f, "{check}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Fatty acid check
+#[derive(Clone, Debug, PartialEq)]
+pub struct Check {
+    pub limits: Limits,
+    /// Sum of the matching values, `None` if missing
+    pub value: Option<f64>,
+    pub status: Status,
+}
+
+impl Check {
+    pub fn passed(&self) -> bool {
+        match self.status {
+            Status::InRange => true,
+            Status::Missing => self.limits.min <= 0.0,
+            Status::Below | Status::Above => false,
+        }
+    }
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let Limits { min, max, .. } = self.limits;
+        let (fatty_acid, ..) = <(String, f64, f64)>::from(self.limits.clone());
+        write!(f, "{fatty_acid} [{min}, {max}]: ")?;
+        match self.value {
+            Some(value) => write!(f, "{value} {}", self.status),
+            None => write!(f, "{}", self.status),
+        }
+    }
+}
+
+/// Check status
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub enum Status {
+    InRange,
+    Below,
+    Above,
+    Missing,
+}
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::InRange => f.write_str("in range"),
+            Self::Below => f.write_str("below range"),
+            Self::Above => f.write_str("above range"),
+            Self::Missing => f.write_str("missing"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    fn standards() -> Standards {
+        Standards::from_ron(
+            r#"[(
+                name: "Test",
+                source: "Test",
+                fatty_acids: [
+                    ("16:0", 5.0, 10.0),
+                    ("18:1", 60.0, 80.0),
+                    ("18:2Δ9,12", 10.0, 20.0),
+                    ("18:3", 0.0, 1.0),
+                    ("20:0", 0.1, 0.5),
+                ],
+            )]"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn ron() {
+        let standards = standards();
+        let standard = standards.get("test").unwrap();
+        assert_eq!(standard.description, "");
+        assert_eq!(standard.fatty_acids[2].fatty_acid, fatty_acid!(18;9,12));
+        let text = standards.to_ron().unwrap();
+        assert_eq!(Standards::from_ron(&text).unwrap(), standards);
+        assert!(Standards::from_ron(r#"[(name: "", fatty_acids: [("18", 0.0, 1.0)])]"#).is_err());
+    }
+
+    #[test]
+    fn check() -> PolarsResult<()> {
+        let standards = standards();
+        let standard = standards.get("Test").unwrap();
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![
+                    fatty_acid!(16),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;11),
+                    fatty_acid!(18;9,12),
+                    fatty_acid!(20),
+                    fatty_acid!(22),
+                ],
+            ),
+            "Value" => [8.0, 60.0, 5.0, 25.0, 0.3, 1.7],
+        }?;
+        let compliance = standard.check(&data_frame, "Value")?;
+        let statuses = compliance
+            .checks
+            .iter()
+            .map(|check| check.status)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            statuses,
+            [
+                Status::InRange,
+                Status::InRange,
+                Status::Above,
+                Status::Missing,
+                Status::InRange,
+            ],
+        );
+        // Positional isomers sum up
+        assert_eq!(compliance.checks[1].value, Some(65.0));
+        assert_eq!(compliance.unlisted, [(fatty_acid!(22), 1.7)]);
+        assert!(!compliance.passed());
+        assert_eq!(compliance.filter(Status::Above).count(), 1);
+        assert_eq!(
+            compliance.checks[2].to_string(),
+            "18:2Δ9,12 [10, 20]: 25 above range",
+        );
+        // Not detected 18:3 passes, not detected 20:0 does not
+        let mut data_frame = data_frame.slice(0, 4);
+        data_frame.with_column(Series::new("Value".into(), [8.0, 60.0, 5.0, 15.0]))?;
+        let compliance = standard.check(&data_frame, "Value")?;
+        assert!(compliance.checks[3].passed());
+        assert!(!compliance.checks[4].passed());
+        assert!(!compliance.passed());
+        Ok(())
+    }
+}
@@ -0,0 +1,173 @@
+use super::{COLUMN, DataFrameExt as _};
+use crate::fatty_acid::composition::{Composition, Unit};
+use polars::prelude::*;
+
+/// Composition unit column name
+pub const UNIT: &str = "Unit";
+
+impl Composition {
+    /// Composition of the fatty acid [`COLUMN`], the `value` column and the
+    /// [`UNIT`] column
+    ///
+    /// Null and duplicate fatty acids and null values are errors. The unit is
+    /// the [`Unit`] label of every row, [`Unit::Area`] without a [`UNIT`]
+    /// column.
+    pub fn from_data_frame(data_frame: &DataFrame, value: &str) -> PolarsResult<Self> {
+        let fatty_acids = data_frame.try_fatty_acid()?.to_vec()?;
+        let values = data_frame[value].cast(&DataType::Float64)?;
+        let mut composition = Self::new(unit(data_frame)?);
+        for (fatty_acid, value) in fatty_acids.into_iter().zip(values.f64()?) {
+            let (Some(fatty_acid), Some(value)) = (fatty_acid, value) else {
+                polars_bail!(ComputeError: "null fatty acid or value");
+            };
+            polars_ensure!(
+                composition.get(&fatty_acid).is_none(),
+                Duplicate: "duplicate fatty acid {fatty_acid:?}",
+            );
+            composition.insert(fatty_acid, value);
+        }
+        Ok(composition)
+    }
+
+    /// Data frame of the fatty acid [`COLUMN`], the `value` column and the
+    /// [`UNIT`] column
+    pub fn to_data_frame(&self, value: &str) -> PolarsResult<DataFrame> {
+        DataFrame::new(vec![
+            Series::new(
+                COLUMN.into(),
+                self.iter()
+                    .map(|(fatty_acid, _)| fatty_acid.clone())
+                    .collect::<Vec<_>>(),
+            )
+            .into(),
+            Series::new(
+                value.into(),
+                self.iter().map(|(_, value)| value).collect::<Vec<_>>(),
+            )
+            .into(),
+            Series::new(UNIT.into(), vec![self.unit.to_string(); self.len()]).into(),
+        ])
+    }
+}
+
+fn unit(data_frame: &DataFrame) -> PolarsResult<Unit> {
+    let Ok(column) = data_frame.column(UNIT) else {
+        return Ok(Unit::default());
+    };
+    let labels = column.str()?.unique()?;
+    polars_ensure!(
+        labels.null_count() == 0,
+        ComputeError: "null composition unit",
+    );
+    polars_ensure!(
+        labels.len() <= 1,
+        ComputeError: "mixed composition units",
+    );
+    match labels.get(0) {
+        Some(label) => label
+            .parse()
+            .map_err(|error| polars_err!(ComputeError: "{error}")),
+        None => Ok(Unit::default()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        fatty_acid,
+        polars::{ExprExt as _, Registry, validate_schema},
+    };
+
+    #[test]
+    fn data_frame() -> PolarsResult<()> {
+        let mut composition = Composition::from_iter([
+            (fatty_acid!(12), 2.0),
+            (fatty_acid!(14), 8.0),
+            (fatty_acid!(16), 24.0),
+            (fatty_acid!(16;9), 1.0),
+            (fatty_acid!(18), 12.0),
+            (fatty_acid!(18;9), 30.0),
+            (fatty_acid!(18;-11), 3.0),
+            (fatty_acid!(18;9,12), 13.0),
+            (fatty_acid!(18;9,12,15), 2.0),
+            (fatty_acid!(20;5,8,11,14,17), 2.0),
+            (fatty_acid!(22;4,7,10,13,16,19), 3.0),
+        ]);
+        composition.unit = Unit::Mole;
+        let data_frame = composition.to_data_frame("Value")?;
+        assert!(validate_schema(&data_frame).is_valid());
+        assert_eq!(
+            Composition::from_data_frame(&data_frame, "Value")?,
+            composition,
+        );
+        // The same indices as the expressions
+        let registry = Registry::default();
+        let exprs = data_frame
+            .clone()
+            .lazy()
+            .select(registry.exprs(&col(COLUMN).fatty_acid(), col("Value")))
+            .collect()?;
+        for (name, value) in composition.indices(&registry) {
+            let expected = exprs[name].cast(&DataType::Float64)?.f64()?.get(0).unwrap();
+            assert!(
+                (value - expected).abs() < 1e-9,
+                "{name}: {value} != {expected}"
+            );
+        }
+        // Duplicates
+        let mut duplicated = data_frame.clone();
+        duplicated.vstack_mut(&data_frame)?;
+        assert!(Composition::from_data_frame(&duplicated, "Value").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unit() -> PolarsResult<()> {
+        let mut composition =
+            Composition::from_iter([(fatty_acid!(16), 40.0), (fatty_acid!(18), 60.0)]);
+        composition.unit = Unit::MilligramsPerGram;
+        let data_frame = composition.to_data_frame("Value")?;
+        assert_eq!(
+            data_frame[UNIT].str()?.get(0),
+            Some(Unit::MilligramsPerGram.to_string().as_str()),
+        );
+        // Without a unit column
+        let area = data_frame.drop(UNIT)?;
+        assert_eq!(
+            Composition::from_data_frame(&area, "Value")?.unit,
+            Unit::Area
+        );
+        // Mixed and unknown units
+        let mut mixed = data_frame.clone();
+        mixed.with_column(Column::new(UNIT.into(), ["mg/g", "mol %"]))?;
+        assert!(Composition::from_data_frame(&mixed, "Value").is_err());
+        let mut unknown = data_frame.clone();
+        unknown.with_column(Column::new(UNIT.into(), ["%", "%"]))?;
+        assert!(Composition::from_data_frame(&unknown, "Value").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn unsorted() -> PolarsResult<()> {
+        let composition = Composition::from_iter([
+            (fatty_acid!(18;12,9), 60.0),
+            (fatty_acid!(18;15,-12,9), 40.0),
+        ]);
+        let data_frame = composition.to_data_frame("Value")?;
+        let round_trip = Composition::from_data_frame(&data_frame, "Value")?;
+        assert_eq!(round_trip, composition);
+        assert_eq!(round_trip.get(&fatty_acid!(18;9,12)), Some(60.0));
+        assert_eq!(round_trip.get(&fatty_acid!(18;9,-12,15)), Some(40.0));
+        // The same fatty acid in another bond order is a duplicate
+        let duplicated = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(18;12,9), fatty_acid!(18;9,12)],
+            ),
+            "Value" => [60.0, 40.0],
+        }?;
+        assert!(Composition::from_data_frame(&duplicated, "Value").is_err());
+        Ok(())
+    }
+}
@@ -1,13 +1,67 @@
 use super::{COLUMN, column::ColumnExt as _, series::FattyAcidSeries};
+use crate::fatty_acid::FattyAcid;
 use polars::prelude::*;
 
 /// Extension methods for [`DataFrame`]
 pub trait DataFrameExt {
+    /// # Panics
+    ///
+    /// Panics if there is no fatty acid [`COLUMN`], see
+    /// [`try_fatty_acid`](Self::try_fatty_acid).
     fn fatty_acid(&self) -> FattyAcidSeries;
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries>;
+
+    /// Fatty acid column by name
+    fn fatty_acid_by_name(&self, name: &str) -> PolarsResult<FattyAcidSeries>;
 }
 
 impl DataFrameExt for DataFrame {
     fn fatty_acid(&self) -> FattyAcidSeries {
         self[COLUMN].fatty_acid()
     }
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries> {
+        self.fatty_acid_by_name(COLUMN)
+    }
+
+    fn fatty_acid_by_name(&self, name: &str) -> PolarsResult<FattyAcidSeries> {
+        self.column(name)?.try_fatty_acid()
+    }
+}
+
+/// A single [`COLUMN`] data frame
+impl FromIterator<FattyAcid> for DataFrame {
+    fn from_iter<T: IntoIterator<Item = FattyAcid>>(iter: T) -> Self {
+        DataFrame::from_iter([iter.into_iter().collect::<Series>()])
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{fatty_acid, polars::SeriesExt as _};
+
+    #[test]
+    fn try_fatty_acid() -> PolarsResult<()> {
+        let data_frame = df! {
+            "Name" => ["Oleic"],
+            "Acid" => Series::new("Acid".into(), vec![fatty_acid!(18;9)]),
+            "Carbons" => [18i64],
+        }?;
+        // Missing column
+        assert!(data_frame.try_fatty_acid().is_err());
+        // Not a struct
+        assert!(data_frame.fatty_acid_by_name("Name").is_err());
+        assert!(data_frame.fatty_acid_by_name("Carbons").is_err());
+        // Wrong field dtype
+        let carbons = Series::new("Carbons".into(), [18i64]);
+        let unsaturated = data_frame["Acid"].struct_()?.field_by_name("Unsaturated")?;
+        let invalid = StructChunked::from_series(COLUMN.into(), 1, [carbons, unsaturated].iter())?;
+        assert!(invalid.into_series().try_fatty_acid().is_err());
+        // By name
+        let fatty_acid = data_frame.fatty_acid_by_name("Acid")?;
+        assert_eq!(fatty_acid.get(0)?, Some(fatty_acid!(18;9)));
+        Ok(())
+    }
 }
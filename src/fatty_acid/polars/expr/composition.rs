@@ -0,0 +1,156 @@
+use super::FattyAcidExpr;
+use crate::fatty_acid::{FattyAcid, derivative::Derivative};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Composition fraction
+#[derive(Clone, Copy, Debug, Deserialize, Eq, Hash, PartialEq, Serialize)]
+pub enum Fraction {
+    /// Area fraction of the derivative, assuming the detector response is
+    /// proportional to the mass
+    Area(Derivative),
+    /// Area fraction of the derivative in a flame ionization detector,
+    /// corrected by the theoretical response factors, see
+    /// [`FattyAcidExpr::response_factor`]
+    Fid(Derivative),
+    /// Mass fraction of the derivative
+    Mass(Derivative),
+    /// Mole fraction
+    Mole,
+}
+
+impl FattyAcidExpr {
+    /// Mass of the derivative, see [`mass`](Self::mass).
+    pub fn derivative_mass(&self, derivative: Derivative) -> Expr {
+        self.mass() + lit(derivative.delta())
+    }
+
+    /// Effective carbons, see [`FattyAcid::effective_carbons`].
+    pub fn effective_carbons(&self, derivative: Derivative) -> Expr {
+        let carbons = derivative.carbons() as i32;
+        when(self.carbons().gt(lit(0u8)))
+            .then(self.carbons() + lit(carbons - 1))
+            .otherwise(lit(carbons))
+    }
+
+    /// Theoretical FID response factor, see [`FattyAcid::response_factor`].
+    pub fn response_factor(&self, derivative: Derivative, reference: &FattyAcid) -> Expr {
+        let reference = reference.mass(derivative) / reference.effective_carbons(derivative) as f64;
+        self.derivative_mass(derivative) / self.effective_carbons(derivative) / lit(reference)
+    }
+
+    /// Convert the composition `from` one fraction `to` another
+    ///
+    /// The result is renormalized to 100 %. The sum runs over the context, so
+    /// the conversion is per group inside `group_by().agg()` or `over()`.
+    pub fn convert(&self, value: Expr, from: Fraction, to: Fraction) -> Expr {
+        let moles = match from {
+            Fraction::Area(derivative) | Fraction::Mass(derivative) => {
+                value / self.derivative_mass(derivative)
+            }
+            Fraction::Fid(derivative) => value / self.effective_carbons(derivative),
+            Fraction::Mole => value,
+        };
+        let converted = match to {
+            Fraction::Area(derivative) | Fraction::Mass(derivative) => {
+                moles * self.derivative_mass(derivative)
+            }
+            Fraction::Fid(derivative) => moles * self.effective_carbons(derivative),
+            Fraction::Mole => moles,
+        };
+        lit(100) * converted.clone() / converted.sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        C4U0, C16U0, fatty_acid,
+        polars::{COLUMN, ExprExt as _},
+    };
+
+    #[test]
+    fn convert() -> PolarsResult<()> {
+        let fatty_acids = [fatty_acid!(16), fatty_acid!(18;9), fatty_acid!(18;9,12)];
+        let data_frame = df! {
+            "Group" => ["A", "A", "A", "B", "B", "B"],
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [fatty_acids.clone(), fatty_acids.clone()].concat(),
+            ),
+            "Area" => [20.0, 50.0, 30.0, 2.0, 5.0, 3.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let fame = Fraction::Area(Derivative::Methyl);
+        let converted = data_frame
+            .lazy()
+            .with_columns([
+                fatty_acid
+                    .convert(col("Area"), fame, Fraction::Mole)
+                    .over([col("Group")])
+                    .alias("Mole"),
+                fatty_acid
+                    .convert(col("Area"), fame, Fraction::Mass(Derivative::Acid))
+                    .over([col("Group")])
+                    .alias("Mass"),
+            ])
+            .with_column(
+                fatty_acid
+                    .convert(col("Mole"), Fraction::Mole, fame)
+                    .over([col("Group")])
+                    .alias("Back"),
+            )
+            .collect()?;
+        // Expected by hand
+        let masses = fatty_acids.map(|fatty_acid: FattyAcid| fatty_acid.mass(Derivative::Methyl));
+        let moles = [20.0, 50.0, 30.0]
+            .iter()
+            .zip(masses)
+            .map(|(area, mass)| area / mass)
+            .collect::<Vec<_>>();
+        let total = moles.iter().sum::<f64>();
+        let mole = converted["Mole"].f64()?;
+        let back = converted["Back"].f64()?;
+        for index in 0..6 {
+            let expected = 100.0 * moles[index % 3] / total;
+            assert!((mole.get(index).unwrap() - expected).abs() < 1e-9);
+            let area = converted["Area"].f64()?.get(index).unwrap();
+            assert!(
+                (back.get(index).unwrap() - area * (1.0 + 9.0 * (index / 3) as f64)).abs() < 1e-9
+            );
+        }
+        // The lightest acid gains in mole fraction.
+        assert!(mole.get(0).unwrap() > 20.0);
+        let mass = converted["Mass"].f64()?;
+        assert!((mass.iter().flatten().sum::<f64>() - 200.0).abs() < 1e-9);
+        Ok(())
+    }
+
+    #[test]
+    fn fid() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(PlSmallStr::EMPTY, vec![C4U0, C16U0]),
+            "Area" => [50.0, 50.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let fame = Derivative::Methyl;
+        let corrected = data_frame
+            .lazy()
+            .select([
+                fatty_acid.response_factor(fame, &C16U0).alias("Factor"),
+                fatty_acid
+                    .convert(col("Area"), Fraction::Fid(fame), Fraction::Mass(fame))
+                    .alias("Mass"),
+            ])
+            .collect()?;
+        let factor = C4U0.response_factor(fame, &C16U0);
+        let factors = corrected["Factor"].f64()?;
+        assert!((factors.get(0).unwrap() - factor).abs() < 1e-12);
+        assert!((factors.get(1).unwrap() - 1.0).abs() < 1e-12);
+        let mass = corrected["Mass"].f64()?;
+        let expected = 100.0 * factor / (factor + 1.0);
+        assert!((mass.get(0).unwrap() - expected).abs() < 1e-9);
+        Ok(())
+    }
+}
@@ -2,6 +2,7 @@ use super::{
     FattyAcidExpr,
     find::{Find, FindByName},
 };
+use crate::fatty_acid::chain_length::{Boundaries, ChainLength, Parity};
 use polars::prelude::*;
 
 // SFA, ∑MUFA, ∑PUFA, ∑n-6 PUFA, ∑n-3 PUFA, and n-6 PUFA/n-3 PUFA
@@ -40,35 +41,112 @@ pub trait Filter {
     /// All unsaturated fatty acids having more than one unsaturated bond.
     fn pufa(&self, expr: Expr) -> Expr;
 
-    /// PUFA n-3, n-6, ...
-    fn pufan(&self, n: u8) -> Expr;
+    /// Chain length class, see [`FattyAcidExpr::is_chain_length`].
+    fn chain_length(&self, chain_length: ChainLength, boundaries: &Boundaries, expr: Expr) -> Expr;
+
+    /// SCFA (Short chain fatty acids) with the default [`Boundaries`]
+    fn scfa(&self, expr: Expr) -> Expr;
+
+    /// MCFA (Medium chain fatty acids) with the default [`Boundaries`]
+    fn mcfa(&self, expr: Expr) -> Expr;
+
+    /// LCFA (Long chain fatty acids) with the default [`Boundaries`]
+    fn lcfa(&self, expr: Expr) -> Expr;
+
+    /// VLCFA (Very long chain fatty acids) with the default [`Boundaries`]
+    fn vlcfa(&self, expr: Expr) -> Expr;
+
+    /// OCFA (Odd chain fatty acids)
+    fn ocfa(&self, expr: Expr) -> Expr;
+
+    /// ∑SCFA
+    fn sum_scfa(&self, expr: Expr) -> Expr;
+
+    /// ∑MCFA
+    fn sum_mcfa(&self, expr: Expr) -> Expr;
+
+    /// ∑LCFA
+    fn sum_lcfa(&self, expr: Expr) -> Expr;
+
+    /// ∑VLCFA
+    fn sum_vlcfa(&self, expr: Expr) -> Expr;
+
+    /// ∑OCFA
+    fn sum_ocfa(&self, expr: Expr) -> Expr;
+
+    /// ω-n (n-n)
+    ///
+    /// All unsaturated fatty acids having the last unsaturated bond `n`
+    /// carbons from the methyl end, see [`FattyAcidExpr::omega`].
+    fn n(&self, n: u8, expr: Expr) -> Expr;
+
+    /// ω-3 (n-3)
+    fn n3(&self, expr: Expr) -> Expr;
+
+    /// ω-6 (n-6)
+    fn n6(&self, expr: Expr) -> Expr;
+
+    /// ω-7 (n-7)
+    fn n7(&self, expr: Expr) -> Expr;
+
+    /// ω-9 (n-9)
+    fn n9(&self, expr: Expr) -> Expr;
+
+    /// ∑n-3
+    fn sum_n3(&self, expr: Expr) -> Expr;
+
+    /// ∑n-6
+    fn sum_n6(&self, expr: Expr) -> Expr;
+
+    /// n-6/n-3
+    /// ∑n-6 / ∑n-3
+    fn n6_n3(&self, expr: Expr) -> Expr;
 
     /// IA (Index of atherogenicity)
-    /// (C12:0 + 4 * C14:0 + C16:0) / ΣUFA
+    ///
+    /// (C12:0 + 4 * C14:0 + C16:0) / (ΣMUFA + ΣPUFA n-6 + ΣPUFA n-3)
+    /// (Ulbricht & Southgate, 1991).
     fn ia(&self, expr: Expr) -> Expr;
 
     /// IT (Index of thrombogenicity)
-    /// (C14:0 + C16:0 + C18:0)/((0.5*ΣMUFA) + (0.5*ΣPUFAN6) + (3*ΣPUFAN3) + (n-3/n-6)]
+    ///
+    /// (C14:0 + C16:0 + C18:0) / (0.5 * ΣMUFA + 0.5 * ΣPUFA n-6 + 3 * ΣPUFA
+    /// n-3 + ΣPUFA n-3 / ΣPUFA n-6) (Ulbricht & Southgate, 1991).
+    ///
+    /// The ΣPUFA n-3 / ΣPUFA n-6 term is 0 without PUFA n-6.
     fn it(&self, expr: Expr) -> Expr;
 
     /// HH (Hypocholesterolemic/hypercholesterolemic ratio)
-    /// (cis-C18:1 + ΣPUFA)/(C12:0 + C14:0 + C16:0)
+    ///
+    /// (cis-9 C18:1 + ΣPUFA n-6 + ΣPUFA n-3) / (C14:0 + C16:0)
+    /// (Santos-Silva et al., 2002).
     fn hh(&self, expr: Expr) -> Expr;
 
     /// Health-promoting index (HPI)
-    /// ΣUFA / (C12:0 + 4 * C14:0 + C16:0)
+    ///
+    /// ΣUFA / (C12:0 + 4 * C14:0 + C16:0) (Chen et al., 2004).
     fn hpi(&self, expr: Expr) -> Expr;
 
     /// Unsaturation index (UI)
+    ///
+    /// Σ(unsaturated bonds * value).
     fn ui(&self, expr: Expr) -> Expr;
 
     /// FLQ (Fish lipid quality/flesh lipid quality)
-    /// (EPA + DHA) / ΣFA
+    ///
+    /// 100 * (C20:5 n-3 + C22:6 n-3) / ΣFA (Abrami et al., 1992).
     fn flq(&self, expr: Expr) -> Expr;
 
     /// TFA (Trans fatty acid)
     ///
-    fn tfa(&self, expr: Expr) -> Expr;
+    /// All fatty acids having at least one trans bond, see
+    /// [`FattyAcidExpr::is_trans`]. Conjugated isomers (CLA) are excluded
+    /// unless `conjugated` is set, as in the Codex definition of trans fatty
+    /// acids.
+    fn tfa(&self, conjugated: bool, expr: Expr) -> Expr;
+
+    /// ∑TFA
+    fn sum_tfa(&self, conjugated: bool, expr: Expr) -> Expr;
 }
 
 impl Filter for FattyAcidExpr {
@@ -112,34 +190,117 @@ impl Filter for FattyAcidExpr {
         expr.filter(self.unsaturated().gt(1))
     }
 
-    fn pufan(&self, n: u8) -> Expr {
-        self.unsaturated().list().eval(
-            col("")
-                .struct_()
-                .field_by_name("Index")
-                .eq(self.carbons() - lit(n)),
-            true,
-        )
+    fn chain_length(&self, chain_length: ChainLength, boundaries: &Boundaries, expr: Expr) -> Expr {
+        expr.filter(self.is_chain_length(chain_length, boundaries))
+    }
+
+    fn scfa(&self, expr: Expr) -> Expr {
+        self.chain_length(ChainLength::Short, &Boundaries::default(), expr)
+    }
+
+    fn mcfa(&self, expr: Expr) -> Expr {
+        self.chain_length(ChainLength::Medium, &Boundaries::default(), expr)
+    }
+
+    fn lcfa(&self, expr: Expr) -> Expr {
+        self.chain_length(ChainLength::Long, &Boundaries::default(), expr)
+    }
+
+    fn vlcfa(&self, expr: Expr) -> Expr {
+        self.chain_length(ChainLength::VeryLong, &Boundaries::default(), expr)
+    }
+
+    fn ocfa(&self, expr: Expr) -> Expr {
+        expr.filter(self.is_parity(Parity::Odd))
+    }
+
+    fn sum_scfa(&self, expr: Expr) -> Expr {
+        self.scfa(expr).sum()
+    }
+
+    fn sum_mcfa(&self, expr: Expr) -> Expr {
+        self.mcfa(expr).sum()
+    }
+
+    fn sum_lcfa(&self, expr: Expr) -> Expr {
+        self.lcfa(expr).sum()
+    }
+
+    fn sum_vlcfa(&self, expr: Expr) -> Expr {
+        self.vlcfa(expr).sum()
+    }
+
+    fn sum_ocfa(&self, expr: Expr) -> Expr {
+        self.ocfa(expr).sum()
+    }
+
+    fn n(&self, n: u8, expr: Expr) -> Expr {
+        expr.filter(self.omega().eq(n))
+    }
+
+    fn n3(&self, expr: Expr) -> Expr {
+        self.n(3, expr)
+    }
+
+    fn n6(&self, expr: Expr) -> Expr {
+        self.n(6, expr)
+    }
+
+    fn n7(&self, expr: Expr) -> Expr {
+        self.n(7, expr)
+    }
+
+    fn n9(&self, expr: Expr) -> Expr {
+        self.n(9, expr)
+    }
+
+    fn sum_n3(&self, expr: Expr) -> Expr {
+        self.n3(expr).sum()
+    }
+
+    fn sum_n6(&self, expr: Expr) -> Expr {
+        self.n6(expr).sum()
+    }
+
+    fn n6_n3(&self, expr: Expr) -> Expr {
+        self.sum_n6(expr.clone()) / self.sum_n3(expr)
     }
 
     fn ia(&self, expr: Expr) -> Expr {
-        (self.c12u0(expr.clone()) + lit(4) * self.c14u0(expr.clone()) + self.c16u0(expr.clone()))
-            / self.ufa(expr)
+        (self.c12u0(expr.clone()).sum()
+            + lit(4) * self.c14u0(expr.clone()).sum()
+            + self.c16u0(expr.clone()).sum())
+            / (self.mufa(expr.clone()).sum()
+                + self.pufa_n(6, expr.clone()).sum()
+                + self.pufa_n(3, expr).sum())
     }
 
     fn it(&self, expr: Expr) -> Expr {
-        (self.c14u0(expr.clone()) + self.c16u0(expr.clone()) + self.c18u0(expr.clone()))
-            / (lit(0.5) * self.ufa(expr) + lit(0.5))
+        let n3 = self.pufa_n(3, expr.clone()).sum();
+        let n6 = self.pufa_n(6, expr.clone()).sum();
+        (self.c14u0(expr.clone()).sum()
+            + self.c16u0(expr.clone()).sum()
+            + self.c18u0(expr.clone()).sum())
+            / (lit(0.5) * self.mufa(expr).sum()
+                + lit(0.5) * n6.clone()
+                + lit(3) * n3.clone()
+                + when(n6.clone().gt(lit(0)))
+                    .then(n3 / n6)
+                    .otherwise(lit(0.0)))
     }
 
     fn hh(&self, expr: Expr) -> Expr {
-        (self.c18u1(expr.clone()) + self.pufa(expr.clone()))
-            / (self.c12u0(expr.clone()) + self.c14u0(expr.clone()) + self.c16u0(expr))
+        (self.c18u1z9(expr.clone()).sum()
+            + self.pufa_n(6, expr.clone()).sum()
+            + self.pufa_n(3, expr.clone()).sum())
+            / (self.c14u0(expr.clone()).sum() + self.c16u0(expr).sum())
     }
 
     fn hpi(&self, expr: Expr) -> Expr {
-        self.ufa(expr.clone())
-            / (self.c12u0(expr.clone()) + lit(4) * self.c14u0(expr.clone()) + self.c16u0(expr))
+        self.ufa(expr.clone()).sum()
+            / (self.c12u0(expr.clone()).sum()
+                + lit(4) * self.c14u0(expr.clone()).sum()
+                + self.c16u0(expr).sum())
     }
 
     fn ui(&self, expr: Expr) -> Expr {
@@ -147,10 +308,305 @@ impl Filter for FattyAcidExpr {
     }
 
     fn flq(&self, expr: Expr) -> Expr {
-        (self.eicosapentaenoic(expr.clone()) + self.docosahexaenoic(expr.clone())) / expr.sum()
+        lit(100)
+            * (self.eicosapentaenoic(expr.clone()).sum() + self.docosahexaenoic(expr.clone()).sum())
+            / expr.sum()
+    }
+
+    fn tfa(&self, conjugated: bool, expr: Expr) -> Expr {
+        let mut predicate = self.is_trans();
+        if !conjugated {
+            predicate = predicate.and(self.is_conjugated().not());
+        }
+        expr.filter(predicate)
+    }
+
+    fn sum_tfa(&self, conjugated: bool, expr: Expr) -> Expr {
+        self.tfa(conjugated, expr).sum()
+    }
+}
+
+impl FattyAcidExpr {
+    /// PUFA ω-n, fatty acids with unknown positions are not counted.
+    pub(crate) fn pufa_n(&self, n: u8, expr: Expr) -> Expr {
+        expr.filter(self.unsaturated().gt(1).and(self.omega().eq(n)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        fatty_acid,
+        polars::{COLUMN, ExprExt as _},
+    };
+
+    fn data_frame() -> PolarsResult<DataFrame> {
+        Ok(df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(16),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;9,12),
+                    fatty_acid!(18;9,12,15),
+                    fatty_acid!(20;5,8,11,14,17),
+                    fatty_acid!(16;9),
+                    fatty_acid!(18;2),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [10.0, 30.0, 20.0, 5.0, 3.0, 2.0, 1.0],
+        }?)
+    }
+
+    #[test]
+    fn omega() -> PolarsResult<()> {
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let omega = data_frame()?
+            .lazy()
+            .select([fatty_acid.omega()])
+            .collect()?;
+        assert_eq!(
+            omega[0].u8()?.iter().collect::<Vec<_>>(),
+            [None, Some(9), Some(6), Some(3), Some(3), Some(7), Some(16)],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn n() -> PolarsResult<()> {
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let sums = data_frame()?
+            .lazy()
+            .select([
+                fatty_acid.n3(value.clone()).sum().alias("N3"),
+                fatty_acid.n6(value.clone()).sum().alias("N6"),
+                fatty_acid.n7(value.clone()).sum().alias("N7"),
+                fatty_acid.n9(value.clone()).sum().alias("N9"),
+                fatty_acid.sum_n3(value.clone()).alias("SumN3"),
+                fatty_acid.sum_n6(value.clone()).alias("SumN6"),
+                fatty_acid.n6_n3(value).alias("N6N3"),
+            ])
+            .collect()?;
+        let value = |name: &str| -> PolarsResult<_> { Ok(sums[name].f64()?.get(0)) };
+        assert_eq!(value("N3")?, Some(8.0));
+        assert_eq!(value("N6")?, Some(20.0));
+        assert_eq!(value("N7")?, Some(2.0));
+        assert_eq!(value("N9")?, Some(30.0));
+        assert_eq!(value("SumN3")?, Some(8.0));
+        assert_eq!(value("SumN6")?, Some(20.0));
+        assert_eq!(value("N6N3")?, Some(2.5));
+        Ok(())
+    }
+
+    /// Worked example, mass % of total fatty acids.
+    ///
+    /// MUFA = 33, PUFA n-6 = 13, PUFA n-3 = 7, UFA = 53.
+    #[test]
+    fn indices() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(12),
+                    fatty_acid!(14),
+                    fatty_acid!(16),
+                    fatty_acid!(18),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;-11),
+                    fatty_acid!(18;9,12),
+                    fatty_acid!(18;9,12,15),
+                    fatty_acid!(20;5,8,11,14),
+                    fatty_acid!(20;5,8,11,14,17),
+                    fatty_acid!(22;4,7,10,13,16,19),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [2.0, 8.0, 25.0, 12.0, 30.0, 3.0, 12.0, 2.0, 1.0, 2.0, 3.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let indices = data_frame
+            .lazy()
+            .select([
+                fatty_acid.ia(value.clone()).alias("IA"),
+                fatty_acid.it(value.clone()).alias("IT"),
+                fatty_acid.hh(value.clone()).alias("HH"),
+                fatty_acid.hpi(value.clone()).alias("HPI"),
+                fatty_acid.ui(value.clone()).alias("UI"),
+                fatty_acid.flq(value).alias("FLQ"),
+            ])
+            .collect()?;
+        let value = |name: &str| -> PolarsResult<_> {
+            Ok(indices[name]
+                .cast(&DataType::Float64)?
+                .f64()?
+                .get(0)
+                .unwrap())
+        };
+        let assert_close = |actual: f64, expected: f64| {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        };
+        // (2 + 4 * 8 + 25) / (33 + 13 + 7)
+        assert_close(value("IA")?, 59.0 / 53.0);
+        // (8 + 25 + 12) / (0.5 * 33 + 0.5 * 13 + 3 * 7 + 7 / 13)
+        assert_close(value("IT")?, 585.0 / 579.0);
+        // (30 + 13 + 7) / (8 + 25), trans-11 C18:1 is not counted
+        assert_close(value("HH")?, 50.0 / 33.0);
+        // 53 / (2 + 4 * 8 + 25)
+        assert_close(value("HPI")?, 53.0 / 59.0);
+        // 33 + 2 * 12 + 3 * 2 + 4 * 1 + 5 * 2 + 6 * 3
+        assert_close(value("UI")?, 95.0);
+        // 100 * (2 + 3) / 100
+        assert_close(value("FLQ")?, 5.0);
+        Ok(())
+    }
+
+    #[test]
+    fn it_without_n6() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![
+                    fatty_acid!(16),
+                    fatty_acid!(18;9),
+                    fatty_acid!(20;5,8,11,14,17),
+                ],
+            ),
+            "Value" => [30.0, 20.0, 10.0],
+        }?;
+        let it = data_frame
+            .lazy()
+            .select([col(COLUMN).fatty_acid().it(col("Value"))])
+            .collect()?;
+        // 30 / (0.5 * 20 + 3 * 10), the ΣPUFA n-3 / ΣPUFA n-6 term is 0
+        let it = it["Value"].cast(&DataType::Float64)?.f64()?.get(0).unwrap();
+        assert!((it - 0.75).abs() < 1e-12, "{it}");
+        Ok(())
+    }
+
+    #[test]
+    fn tfa() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(18),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;-9),
+                    fatty_acid!(18;-9,-12),
+                    fatty_acid!(18;9,-11),
+                    fatty_acid!(18;9,12,-15),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [1.0, 2.0, 4.0, 8.0, 16.0, 32.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let data_frame = data_frame
+            .lazy()
+            .select([
+                fatty_acid.is_cis().alias("Cis"),
+                fatty_acid.is_trans().alias("Trans"),
+                fatty_acid.trans_count().alias("TransCount"),
+                fatty_acid.is_conjugated().alias("Conjugated"),
+                fatty_acid.sum_tfa(false, value.clone()).alias("TFA"),
+                fatty_acid.sum_tfa(true, value).alias("TFA+CLA"),
+            ])
+            .collect()?;
+        let bools =
+            |name: &str| -> PolarsResult<Vec<_>> { Ok(data_frame[name].bool()?.iter().collect()) };
+        let (t, f) = (Some(true), Some(false));
+        assert_eq!(bools("Cis")?, [f, t, f, f, f, f]);
+        assert_eq!(bools("Trans")?, [f, f, t, t, t, t]);
+        assert_eq!(bools("Conjugated")?, [f, f, f, f, t, f]);
+        assert_eq!(
+            data_frame["TransCount"].u8()?.iter().collect::<Vec<_>>(),
+            [Some(0), Some(0), Some(1), Some(2), Some(1), Some(1)],
+        );
+        assert_eq!(data_frame["TFA"].f64()?.get(0), Some(44.0));
+        assert_eq!(data_frame["TFA+CLA"].f64()?.get(0), Some(60.0));
+        Ok(())
     }
 
-    fn tfa(&self, expr: Expr) -> Expr {
-        expr.filter(self.carbons().eq(18).and(self.unsaturated().eq(3)))
+    #[test]
+    fn chain_length() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(4),
+                    fatty_acid!(8),
+                    fatty_acid!(12),
+                    fatty_acid!(15),
+                    fatty_acid!(18;9),
+                    fatty_acid!(24;15),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [1.0, 2.0, 4.0, 8.0, 16.0, 32.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let boundaries = Boundaries {
+            medium: 10,
+            long: 16,
+            very_long: 20,
+        };
+        let classes = data_frame
+            .clone()
+            .lazy()
+            .select([
+                fatty_acid
+                    .chain_length_class(&Boundaries::default())
+                    .alias("Default"),
+                fatty_acid.chain_length_class(&boundaries).alias("Custom"),
+                fatty_acid.parity().alias("Parity"),
+            ])
+            .collect()?;
+        let strings = |name: &str| -> PolarsResult<Vec<_>> {
+            Ok(classes[name]
+                .str()?
+                .iter()
+                .map(|text| text.unwrap().to_owned())
+                .collect())
+        };
+        assert_eq!(
+            strings("Default")?,
+            ["SCFA", "MCFA", "MCFA", "LCFA", "LCFA", "VLCFA"],
+        );
+        assert_eq!(
+            strings("Custom")?,
+            ["SCFA", "SCFA", "MCFA", "MCFA", "LCFA", "VLCFA"],
+        );
+        assert_eq!(
+            strings("Parity")?,
+            ["ECFA", "ECFA", "ECFA", "OCFA", "ECFA", "ECFA"],
+        );
+        let sums = data_frame
+            .lazy()
+            .select([
+                fatty_acid.sum_scfa(value.clone()).alias("SCFA"),
+                fatty_acid.sum_mcfa(value.clone()).alias("MCFA"),
+                fatty_acid.sum_lcfa(value.clone()).alias("LCFA"),
+                fatty_acid.sum_vlcfa(value.clone()).alias("VLCFA"),
+                fatty_acid.sum_ocfa(value.clone()).alias("OCFA"),
+                fatty_acid
+                    .chain_length(ChainLength::Short, &boundaries, value)
+                    .sum()
+                    .alias("Custom SCFA"),
+            ])
+            .collect()?;
+        let value = |name: &str| -> PolarsResult<_> { Ok(sums[name].f64()?.get(0)) };
+        assert_eq!(value("SCFA")?, Some(1.0));
+        assert_eq!(value("MCFA")?, Some(6.0));
+        assert_eq!(value("LCFA")?, Some(24.0));
+        assert_eq!(value("VLCFA")?, Some(32.0));
+        assert_eq!(value("OCFA")?, Some(8.0));
+        assert_eq!(value("Custom SCFA")?, Some(3.0));
+        Ok(())
     }
 }
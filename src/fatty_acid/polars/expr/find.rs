@@ -1,7 +1,13 @@
 use super::FattyAcidExpr;
+use crate::fatty_acid::fatty_acid;
 use polars::prelude::*;
 
 /// Find
+///
+/// The positional finds (e.g. C18:1Δ9) match bonds of cis or unknown
+/// isomerism, trans bonds do not match. Fatty acids without known positions
+/// match by the number of carbons and unsaturated bonds, see
+/// [`FattyAcidExpr::positional`].
 pub trait Find {
     /// C12:0
     fn c12u0(&self, expr: Expr) -> Expr;
@@ -18,6 +24,9 @@ pub trait Find {
     /// C18:1
     fn c18u1(&self, expr: Expr) -> Expr;
 
+    /// C18:1 (n-9) (w-9)
+    fn c18u1z9(&self, expr: Expr) -> Expr;
+
     /// C18:2 (n-6) (w-6)
     fn c18u2z9z12(&self, expr: Expr) -> Expr;
 
@@ -52,25 +61,34 @@ impl Find for FattyAcidExpr {
         expr.filter(self.carbons().eq(18).and(self.unsaturated().eq(1)))
     }
 
+    fn c18u1z9(&self, expr: Expr) -> Expr {
+        expr.filter(self.positional(&fatty_acid!(18;9)))
+    }
+
     fn c18u2z9z12(&self, expr: Expr) -> Expr {
-        expr.filter(self.carbons().eq(18).and(self.unsaturated().eq(2)))
+        expr.filter(self.positional(&fatty_acid!(18;9,12)))
     }
 
     fn c18u3z9z12z15(&self, expr: Expr) -> Expr {
-        expr.filter(self.carbons().eq(18).and(self.unsaturated().eq(3)))
+        expr.filter(self.positional(&fatty_acid!(18;9,12,15)))
     }
 
     fn c20u5z5z8z11z14z17(&self, expr: Expr) -> Expr {
-        expr.filter(self.carbons().eq(20).and(self.unsaturated().eq(5)))
+        expr.filter(self.positional(&fatty_acid!(20;5,8,11,14,17)))
     }
 
     fn c22u6z4z7z10z13z16z19(&self, expr: Expr) -> Expr {
-        expr.filter(self.carbons().eq(22).and(self.unsaturated().eq(6)))
+        expr.filter(self.positional(&fatty_acid!(22;4,7,10,13,16,19)))
     }
 }
 
 /// Find by name
 pub trait FindByName: Find {
+    /// Oleic acid (OA)
+    fn oleic(&self, expr: Expr) -> Expr {
+        self.c18u1z9(expr)
+    }
+
     /// Linoleic acid (LA)
     fn linoleic(&self, expr: Expr) -> Expr {
         self.c18u2z9z12(expr)
@@ -93,3 +111,49 @@ pub trait FindByName: Find {
 }
 
 impl<T: Find> FindByName for T {}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        FattyAcid, Unsaturated, Unsaturation,
+        polars::{COLUMN, ExprExt as _},
+    };
+
+    #[test]
+    fn linoleic() -> PolarsResult<()> {
+        let unknown = |index| Unsaturated {
+            index,
+            isomerism: None,
+            unsaturation: Some(Unsaturation::One),
+        };
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![
+                    fatty_acid!(18;9,12),
+                    // Without positions
+                    FattyAcid {
+                        carbons: 18,
+                        unsaturated: vec![unknown(None), unknown(None)],
+                    },
+                    // Unknown isomerism
+                    FattyAcid {
+                        carbons: 18,
+                        unsaturated: vec![unknown(Some(9)), unknown(Some(12))],
+                    },
+                    fatty_acid!(18;-9,-12),
+                    fatty_acid!(18;9,-11),
+                    fatty_acid!(18;9),
+                ],
+            ),
+            "Value" => [1.0, 2.0, 4.0, 8.0, 16.0, 32.0],
+        }?;
+        let linoleic = data_frame
+            .lazy()
+            .select([col(COLUMN).fatty_acid().linoleic(col("Value")).sum()])
+            .collect()?;
+        assert_eq!(linoleic["Value"].f64()?.get(0), Some(7.0));
+        Ok(())
+    }
+}
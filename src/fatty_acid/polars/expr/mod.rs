@@ -1,15 +1,98 @@
-use crate::r#const::relative_atomic_mass::{C, H, O};
+use crate::{
+    r#const::relative_atomic_mass::{C, H, O},
+    fatty_acid::{
+        FattyAcid, Isomerism,
+        chain_length::{Boundaries, ChainLength, Parity},
+        display::{DisplayWithOptions as _, Options},
+        ecl::Calibration,
+        parse::{Format, parse},
+        polars::{ColumnExt as _, DATA_TYPE},
+    },
+};
 use polars::prelude::*;
 
 /// Extension methods for [`Expr`]
 pub trait ExprExt {
     fn fatty_acid(self) -> FattyAcidExpr;
+
+    /// Parse a string column into fatty acids
+    ///
+    /// Unparseable rows are null, or an error if `strict`.
+    fn to_fatty_acid(self, format: Format, strict: bool) -> Expr;
+
+    /// Parse errors of a string column
+    ///
+    /// Null for parseable rows, see [`to_fatty_acid`](Self::to_fatty_acid).
+    fn to_fatty_acid_error(self, format: Format) -> Expr;
+
+    /// ECL of a retention time column, see [`Calibration::ecl`].
+    fn ecl(self, calibration: Calibration) -> Expr;
 }
 
 impl ExprExt for Expr {
     fn fatty_acid(self) -> FattyAcidExpr {
         FattyAcidExpr(self)
     }
+
+    fn to_fatty_acid(self, format: Format, strict: bool) -> Expr {
+        self.map(
+            move |column| {
+                let fatty_acids = column
+                    .as_materialized_series()
+                    .str()?
+                    .iter()
+                    .enumerate()
+                    .map(
+                        |(row, text)| match text.map(|text| (text, parse(text, format))) {
+                            Some((_, Ok(fatty_acid))) => Ok(Some(fatty_acid)),
+                            Some((text, Err(error))) if strict => {
+                                polars_bail!(ComputeError: r#"row {row}: "{text}": {error}"#)
+                            }
+                            _ => Ok(None),
+                        },
+                    )
+                    .collect::<PolarsResult<Vec<_>>>()?;
+                Ok(Some(Column::new(column.name().clone(), fatty_acids)))
+            },
+            GetOutput::from_type(DATA_TYPE.clone()),
+        )
+    }
+
+    fn to_fatty_acid_error(self, format: Format) -> Expr {
+        self.map(
+            move |column| {
+                Ok(Some(
+                    column
+                        .as_materialized_series()
+                        .str()?
+                        .iter()
+                        .map(|text| Some(parse(text?, format).err()?.to_string()))
+                        .collect::<StringChunked>()
+                        .with_name(column.name().clone())
+                        .into_column(),
+                ))
+            },
+            GetOutput::from_type(DataType::String),
+        )
+    }
+
+    fn ecl(self, calibration: Calibration) -> Expr {
+        self.map(
+            move |column| {
+                Ok(Some(
+                    column
+                        .cast(&DataType::Float64)?
+                        .f64()?
+                        .iter()
+                        .map(|retention_time| calibration.ecl(retention_time?))
+                        .collect::<Float64Chunked>()
+                        .with_name(column.name().clone())
+                        .into_column(),
+                ))
+            },
+            GetOutput::from_type(DataType::Float64),
+        )
+    }
 }
 
 /// Fatty acid [`Expr`]
@@ -28,6 +111,52 @@ impl FattyAcidExpr {
         self.0.clone().struct_().field_by_name("Carbons")
     }
 
+    /// Chain length class
+    ///
+    /// The [`ChainLength`] label (`SCFA`, `MCFA`, `LCFA`, `VLCFA`).
+    pub fn chain_length_class(&self, boundaries: &Boundaries) -> Expr {
+        let carbons = self.carbons();
+        when(carbons.clone().lt(lit(boundaries.medium)))
+            .then(lit(ChainLength::Short.to_string()))
+            .when(carbons.clone().lt(lit(boundaries.long)))
+            .then(lit(ChainLength::Medium.to_string()))
+            .when(carbons.lt(lit(boundaries.very_long)))
+            .then(lit(ChainLength::Long.to_string()))
+            .otherwise(lit(ChainLength::VeryLong.to_string()))
+    }
+
+    /// Chain length
+    ///
+    /// The fatty acid belongs to the chain length class.
+    pub fn is_chain_length(&self, chain_length: ChainLength, boundaries: &Boundaries) -> Expr {
+        let (from, to) = boundaries.range(chain_length);
+        let predicate = self.carbons().gt_eq(lit(from));
+        match to {
+            Some(to) => predicate.and(self.carbons().lt(lit(to))),
+            None => predicate,
+        }
+    }
+
+    /// Parity
+    ///
+    /// The [`Parity`] label (`OCFA`, `ECFA`).
+    pub fn parity(&self) -> Expr {
+        when(self.is_parity(Parity::Even))
+            .then(lit(Parity::Even.to_string()))
+            .otherwise(lit(Parity::Odd.to_string()))
+    }
+
+    /// Parity
+    ///
+    /// The number of carbons has the parity, the same as [`Parity::new`].
+    pub fn is_parity(&self, parity: Parity) -> Expr {
+        let even = (self.carbons() % lit(2u8)).eq(lit(0u8));
+        match parity {
+            Parity::Odd => even.not(),
+            Parity::Even => even,
+        }
+    }
+
     /// Unsaturated
     ///
     /// The number of unsaturated bonds.
@@ -53,6 +182,169 @@ impl FattyAcidExpr {
             .list()
             .sum()
     }
+
+    /// ω (n-)
+    ///
+    /// The position of the last unsaturated bond counted from the methyl end.
+    /// Null for saturated fatty acids and unknown positions.
+    pub fn omega(&self) -> Expr {
+        let indices = self
+            .0
+            .clone()
+            .struct_()
+            .field_by_name("Unsaturated")
+            .list()
+            .eval(col("").struct_().field_by_name("Index"), true);
+        when(
+            indices
+                .clone()
+                .list()
+                .eval(col("").is_null(), true)
+                .list()
+                .any(),
+        )
+        .then(lit(NULL))
+        .otherwise(self.carbons() - indices.list().max())
+        .cast(DataType::UInt8)
+    }
+
+    /// Matches
+    ///
+    /// The fatty acid has the same carbons and unsaturated bonds. Unknown bond
+    /// indices and isomerism of the pattern match any, unknown ones of the
+    /// fatty acid match only those. See [`positional`](Self::positional) for
+    /// the lookup that also counts fatty acids of unknown positions.
+    pub fn matches(&self, fatty_acid: &FattyAcid) -> Expr {
+        let mut predicate = self.carbons().eq(lit(fatty_acid.carbons)).and(
+            self.unsaturated()
+                .eq(lit(fatty_acid.unsaturated.len() as u32)),
+        );
+        for unsaturated in &fatty_acid.unsaturated {
+            let Some(index) = unsaturated.index else {
+                continue;
+            };
+            let mut bond = col("").struct_().field_by_name("Index").eq(lit(index));
+            if let Some(isomerism) = unsaturated.isomerism {
+                bond = bond.and(
+                    col("")
+                        .struct_()
+                        .field_by_name("Isomerism")
+                        .eq(lit(i8::from(isomerism))),
+                );
+            }
+            predicate = predicate.and(
+                self.0
+                    .clone()
+                    .struct_()
+                    .field_by_name("Unsaturated")
+                    .list()
+                    .eval(bond, true)
+                    .list()
+                    .any(),
+            );
+        }
+        predicate
+    }
+
+    /// Positional
+    ///
+    /// The fatty acid has the same carbons and number of unsaturated bonds,
+    /// and a bond at each known index of the pattern. Unlike
+    /// [`matches`](Self::matches), a bond of unknown isomerism matches any
+    /// isomerism of the pattern, and a fatty acid without known positions
+    /// matches by the number of bonds alone. This is the lookup of
+    /// [`Find`](super::Find), the same as [`FattyAcid::positional`].
+    pub fn positional(&self, pattern: &FattyAcid) -> Expr {
+        let unsaturated = || self.0.clone().struct_().field_by_name("Unsaturated").list();
+        let count = self
+            .carbons()
+            .eq(lit(pattern.carbons))
+            .and(self.unsaturated().eq(lit(pattern.unsaturated.len() as u32)));
+        let unknown = unsaturated()
+            .eval(col("").struct_().field_by_name("Index").is_null(), true)
+            .list()
+            .all();
+        let mut known = lit(true);
+        for bond in &pattern.unsaturated {
+            let Some(index) = bond.index else {
+                continue;
+            };
+            let mut predicate = col("").struct_().field_by_name("Index").eq(lit(index));
+            if let Some(isomerism) = bond.isomerism {
+                let field = col("").struct_().field_by_name("Isomerism");
+                predicate = predicate.and(
+                    field
+                        .clone()
+                        .is_null()
+                        .or(field.eq(lit(i8::from(isomerism)))),
+                );
+            }
+            known = known.and(unsaturated().eval(predicate, true).list().any());
+        }
+        count.and(unknown.or(known))
+    }
+
+    /// Cis
+    ///
+    /// All unsaturated bonds are known to be cis.
+    pub fn is_cis(&self) -> Expr {
+        self.unsaturated()
+            .gt(0)
+            .and(self.isomerism_count(Isomerism::Cis).eq(self.unsaturated()))
+    }
+
+    /// Trans
+    ///
+    /// At least one unsaturated bond is trans.
+    pub fn is_trans(&self) -> Expr {
+        self.trans_count().gt(0)
+    }
+
+    /// Trans count
+    ///
+    /// The number of trans unsaturated bonds.
+    pub fn trans_count(&self) -> Expr {
+        self.isomerism_count(Isomerism::Trans)
+    }
+
+    /// Conjugated
+    ///
+    /// At least two unsaturated bonds are adjacent, not interrupted by a
+    /// methylene group (e.g. CLA 18:2Δ9,11t).
+    pub fn is_conjugated(&self) -> Expr {
+        let index = col("")
+            .struct_()
+            .field_by_name("Index")
+            .strict_cast(DataType::Int32)
+            .sort(Default::default());
+        self.0
+            .clone()
+            .struct_()
+            .field_by_name("Unsaturated")
+            .list()
+            .eval((index.clone() - index.shift(lit(1))).eq(lit(2)), true)
+            .list()
+            .any()
+    }
+
+    fn isomerism_count(&self, isomerism: Isomerism) -> Expr {
+        self.0
+            .clone()
+            .struct_()
+            .field_by_name("Unsaturated")
+            .list()
+            .eval(
+                col("")
+                    .struct_()
+                    .field_by_name("Isomerism")
+                    .eq(lit(i8::from(isomerism)))
+                    .strict_cast(DataType::UInt8),
+                true,
+            )
+            .list()
+            .sum()
+            .strict_cast(DataType::UInt8)
+    }
     // /// Double bounds count
     // pub fn d(&self) -> Expr {
     //     self.0
@@ -108,7 +400,7 @@ impl FattyAcidExpr {
 
     /// Mass
     pub fn mass(&self) -> Expr {
-        self.carbons() * lit(C) + self.hydrogens() * lit(H) + lit(2) * lit(O)
+        self.carbons() * lit(C) + self.hydrogens() * lit(H) + lit(2. * O)
     }
 
     /// Saturated
@@ -142,6 +434,190 @@ impl FattyAcidExpr {
     }
 }
 
+impl FattyAcidExpr {
+    /// Format
+    ///
+    /// Renders the fatty acids as strings with the display options, see
+    /// [`Display`](crate::fatty_acid::display::Display).
+    pub fn format(&self, options: Options) -> Expr {
+        self.0.clone().map(
+            move |column| {
+                let fatty_acids = column.try_fatty_acid()?.to_vec()?;
+                Ok(Some(
+                    fatty_acids
+                        .iter()
+                        .map(|fatty_acid| Some(fatty_acid.as_ref()?.display(options).to_string()))
+                        .collect::<StringChunked>()
+                        .with_name(column.name().clone())
+                        .into_column(),
+                ))
+            },
+            GetOutput::from_type(DataType::String),
+        )
+    }
+}
+
+pub mod composition;
 pub mod filter;
 pub mod find;
 pub mod short;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        C16U0, C18U0, Unsaturated,
+        display::{COMMON, ID},
+        ecl::Mode,
+        fatty_acid,
+        polars::COLUMN,
+    };
+
+    #[test]
+    fn format() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![Some(fatty_acid!(18)), None, Some(fatty_acid!(18;9,-12))],
+            ),
+        }?;
+        let formatted = data_frame
+            .lazy()
+            .select([
+                col(COLUMN).fatty_acid().format(COMMON).alias("Common"),
+                col(COLUMN)
+                    .fatty_acid()
+                    .format(Options {
+                        alternate: true,
+                        width: Some(2),
+                        ..COMMON
+                    })
+                    .alias("Alternate"),
+                col(COLUMN)
+                    .fatty_acid()
+                    .format(Options {
+                        alternate: true,
+                        ..ID
+                    })
+                    .alias("Id"),
+            ])
+            .collect()?;
+        let values = |name: &str| -> PolarsResult<Vec<Option<String>>> {
+            Ok(formatted[name]
+                .str()?
+                .iter()
+                .map(|value| value.map(ToOwned::to_owned))
+                .collect())
+        };
+        assert_eq!(
+            values("Common")?,
+            [Some("18:0".to_owned()), None, Some("18:2".to_owned())],
+        );
+        assert_eq!(
+            values("Alternate")?,
+            [
+                Some("18:00".to_owned()),
+                None,
+                Some("18:02Δ09,12t".to_owned())
+            ],
+        );
+        assert_eq!(
+            values("Id")?,
+            [
+                Some("c18u0".to_owned()),
+                None,
+                Some("c18u2c9t12".to_owned())
+            ],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn to_fatty_acid() -> PolarsResult<()> {
+        let data_frame = df! {
+            "Name" => [Some("18:1Δ9"), Some("C18:2n6"), Some("c18u1c9"), Some("?"), None],
+        }?;
+        let parsed = data_frame
+            .clone()
+            .lazy()
+            .select([
+                col("Name").to_fatty_acid(Format::Auto, false).alias(COLUMN),
+                col("Name").to_fatty_acid_error(Format::Auto).alias("Error"),
+            ])
+            .collect()?;
+        assert_eq!(parsed[COLUMN].dtype(), &*DATA_TYPE);
+        assert_eq!(
+            parsed[COLUMN].fatty_acid().to_vec()?,
+            [
+                Some(fatty_acid!(18;9)),
+                Some(fatty_acid!(18;9,12)),
+                Some(fatty_acid!(18;9)),
+                None,
+                None,
+            ],
+        );
+        assert_eq!(
+            parsed["Error"].str()?.iter().collect::<Vec<_>>(),
+            [None, None, None, Some("expected number at 0"), None],
+        );
+        // Strict
+        let error = data_frame
+            .lazy()
+            .select([col("Name").to_fatty_acid(Format::Auto, true)])
+            .collect()
+            .unwrap_err();
+        assert!(error.to_string().contains(r#"row 3: "?""#));
+        Ok(())
+    }
+
+    #[test]
+    fn matches() -> PolarsResult<()> {
+        let data_frame = DataFrame::from_iter([
+            fatty_acid!(18;9),
+            fatty_acid!(18;-9),
+            fatty_acid!(18;11),
+            fatty_acid!(18;9,12),
+            FattyAcid {
+                carbons: 18,
+                unsaturated: vec![Unsaturated::default()],
+            },
+        ]);
+        let matches = |pattern: &FattyAcid| -> PolarsResult<Vec<_>> {
+            let matches = data_frame
+                .clone()
+                .lazy()
+                .select([col(COLUMN).fatty_acid().matches(pattern)])
+                .collect()?;
+            Ok(matches[0].bool()?.iter().collect())
+        };
+        let any = Some(true);
+        let not = Some(false);
+        assert_eq!(matches(&fatty_acid!(18;9))?, [any, not, not, not, not]);
+        assert_eq!(matches(&fatty_acid!(18;-9))?, [not, any, not, not, not]);
+        // Unknown position
+        let pattern = FattyAcid {
+            carbons: 18,
+            unsaturated: vec![Unsaturated::default()],
+        };
+        assert_eq!(matches(&pattern)?, [any, any, any, not, any]);
+        Ok(())
+    }
+
+    #[test]
+    fn ecl() -> PolarsResult<()> {
+        let calibration =
+            Calibration::new([(C16U0, 10.0), (C18U0, 12.0)], Mode::Programmed).unwrap();
+        let data_frame = df! {
+            "RetentionTime" => [Some(10.0), Some(12.4), None],
+        }?;
+        let ecl = data_frame
+            .lazy()
+            .select([col("RetentionTime").ecl(calibration)])
+            .collect()?;
+        let ecl = ecl["RetentionTime"].f64()?;
+        assert_eq!(ecl.get(0), Some(16.0));
+        assert!((ecl.get(1).unwrap() - 18.4).abs() < 1e-12);
+        assert_eq!(ecl.get(2), None);
+        Ok(())
+    }
+}
@@ -0,0 +1,183 @@
+use super::{COLUMN, DataFrameExt as _};
+use crate::fatty_acid::identification::{Assignment, Entry, Library};
+use polars::prelude::*;
+
+/// Library phase column name
+pub const PHASE: &str = "Phase";
+/// Library ECL column name
+pub const ECL: &str = "ECL";
+
+impl Library {
+    /// Library of the phase
+    ///
+    /// The data frame has the fatty acid [`COLUMN`], the [`PHASE`] and the
+    /// [`ECL`] columns.
+    pub fn new(data_frame: &DataFrame, phase: &str) -> PolarsResult<Self> {
+        let data_frame = data_frame
+            .clone()
+            .lazy()
+            .filter(col(PHASE).eq(lit(phase)))
+            .collect()?;
+        let fatty_acids = data_frame.try_fatty_acid()?.to_vec()?;
+        let ecls = data_frame[ECL].cast(&DataType::Float64)?;
+        let entries = fatty_acids
+            .into_iter()
+            .zip(ecls.f64()?)
+            .filter_map(|(fatty_acid, ecl)| {
+                Some(Entry {
+                    fatty_acid: fatty_acid?,
+                    ecl: ecl?,
+                })
+            })
+            .collect();
+        Ok(Self { entries })
+    }
+
+    /// Library of the phase from a RON serialized data frame, see
+    /// [`new`](Self::new).
+    pub fn from_ron(text: &str, phase: &str) -> PolarsResult<Self> {
+        let data_frame = ron::de::from_str::<DataFrame>(text)
+            .map_err(|error| polars_err!(ComputeError: "deserialize library: {error}"))?;
+        Self::new(&data_frame, phase)
+    }
+
+    /// Identify the peaks
+    ///
+    /// Adds the identified fatty acid [`COLUMN`], the `Delta` and `Score`
+    /// columns, see [`Candidate`](crate::fatty_acid::identification::Candidate).
+    /// See [`rank`](Self::rank) for all the candidates of each peak.
+    pub fn identify(
+        &self,
+        peaks: &DataFrame,
+        ecl: &str,
+        tolerance: f64,
+        assignment: Assignment,
+    ) -> PolarsResult<DataFrame> {
+        let ecls = peaks[ecl]
+            .cast(&DataType::Float64)?
+            .f64()?
+            .iter()
+            .map(|ecl| ecl.unwrap_or(f64::NAN))
+            .collect::<Vec<_>>();
+        let assigned = self.assign(&ecls, tolerance, assignment);
+        let mut identified = peaks.clone();
+        identified.with_column(Series::new(
+            COLUMN.into(),
+            assigned
+                .iter()
+                .map(|candidate| Some(candidate.as_ref()?.fatty_acid.clone()))
+                .collect::<Vec<_>>(),
+        ))?;
+        identified.with_column(Series::new(
+            "Delta".into(),
+            assigned
+                .iter()
+                .map(|candidate| Some(candidate.as_ref()?.delta))
+                .collect::<Vec<_>>(),
+        ))?;
+        identified.with_column(Series::new(
+            "Score".into(),
+            assigned
+                .iter()
+                .map(|candidate| Some(candidate.as_ref()?.score))
+                .collect::<Vec<_>>(),
+        ))?;
+        Ok(identified)
+    }
+
+    /// Rank the candidates of the peaks
+    ///
+    /// A long data frame of the `Peak` row index, the `Rank` (1 is the best),
+    /// the candidate fatty acid [`COLUMN`], the library [`ECL`], the `Delta`
+    /// and `Score` columns, see [`candidates`](Self::candidates). A peak
+    /// without candidates has no rows.
+    pub fn rank(&self, peaks: &DataFrame, ecl: &str, tolerance: f64) -> PolarsResult<DataFrame> {
+        let ecls = peaks[ecl].cast(&DataType::Float64)?;
+        let mut ranked = Vec::new();
+        for (peak, ecl) in ecls.f64()?.iter().enumerate() {
+            let Some(ecl) = ecl else {
+                continue;
+            };
+            for (rank, candidate) in self.candidates(ecl, tolerance).into_iter().enumerate() {
+                ranked.push((peak as u32, rank as u32 + 1, candidate));
+            }
+        }
+        df! {
+            "Peak" => ranked.iter().map(|(peak, ..)| *peak).collect::<Vec<_>>(),
+            "Rank" => ranked.iter().map(|(_, rank, _)| *rank).collect::<Vec<_>>(),
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                ranked
+                    .iter()
+                    .map(|(.., candidate)| candidate.fatty_acid.clone())
+                    .collect::<Vec<_>>(),
+            ),
+            ECL => ranked.iter().map(|(.., candidate)| candidate.ecl).collect::<Vec<_>>(),
+            "Delta" => ranked.iter().map(|(.., candidate)| candidate.delta).collect::<Vec<_>>(),
+            "Score" => ranked.iter().map(|(.., candidate)| candidate.score).collect::<Vec<_>>(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn identify() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(16), fatty_acid!(18;9), fatty_acid!(16), fatty_acid!(18;9)],
+            ),
+            PHASE => ["CP-Sil 88", "CP-Sil 88", "DB-23", "DB-23"],
+            ECL => [16.0, 18.2, 16.0, 18.4],
+        }?;
+        let text = ron::ser::to_string(&data_frame).unwrap();
+        let library = Library::from_ron(&text, "DB-23")?;
+        assert_eq!(library.entries.len(), 2);
+        assert_eq!(library.entries[1].ecl, 18.4);
+        let peaks = df! {
+            "RetentionTime" => [10.0, 12.0, 13.0],
+            "ECL" => [Some(16.02), Some(18.45), None],
+        }?;
+        let identified = library.identify(&peaks, "ECL", 0.1, Assignment::Optimal)?;
+        assert_eq!(
+            identified.fatty_acid().to_vec()?,
+            [Some(fatty_acid!(16)), Some(fatty_acid!(18;9)), None],
+        );
+        let score = identified["Score"].f64()?;
+        assert!((score.get(0).unwrap() - 0.8).abs() < 1e-9);
+        assert_eq!(score.get(2), None);
+        Ok(())
+    }
+
+    #[test]
+    fn rank() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(18), fatty_acid!(18;-9), fatty_acid!(18;9)],
+            ),
+            PHASE => ["DB-23"; 3],
+            ECL => [18.0, 18.3, 18.4],
+        }?;
+        let library = Library::new(&data_frame, "DB-23")?;
+        let peaks = df! {
+            "ECL" => [Some(18.33), None, Some(17.0)],
+        }?;
+        let ranked = library.rank(&peaks, "ECL", 0.2)?;
+        assert_eq!(ranked["Peak"].u32()?.to_vec(), [Some(0), Some(0)]);
+        assert_eq!(ranked["Rank"].u32()?.to_vec(), [Some(1), Some(2)]);
+        assert_eq!(ranked[ECL].f64()?.to_vec(), [Some(18.3), Some(18.4)]);
+        assert_eq!(
+            ranked.fatty_acid().to_vec()?,
+            [Some(fatty_acid!(18;-9)), Some(fatty_acid!(18;9))],
+        );
+        let empty = library.rank(&peaks, "ECL", 0.01)?;
+        assert_eq!(empty.height(), 0);
+        assert_eq!(empty.schema().get(COLUMN), ranked.schema().get(COLUMN));
+        Ok(())
+    }
+}
@@ -0,0 +1,356 @@
+use super::{FattyAcidExpr, Filter as _};
+use crate::fatty_acid::index::{Comparison, Condition, Formula, Index, Registry, Term};
+use polars::prelude::*;
+
+impl Registry {
+    /// Expressions of all indices, see [`Index::expr`].
+    pub fn exprs(&self, fatty_acid: &FattyAcidExpr, value: Expr) -> Vec<Expr> {
+        self.indices
+            .iter()
+            .map(|index| index.expr(fatty_acid, value.clone()))
+            .collect()
+    }
+}
+
+impl Index {
+    /// Compile to an expression named after the index.
+    pub fn expr(&self, fatty_acid: &FattyAcidExpr, value: Expr) -> Expr {
+        self.formula.expr(fatty_acid, value).alias(&self.name)
+    }
+}
+
+impl Formula {
+    /// Compile to an expression, the same as [`evaluate`](Self::evaluate).
+    pub fn expr(&self, fatty_acid: &FattyAcidExpr, value: Expr) -> Expr {
+        match self {
+            Self::Constant(constant) => lit(*constant),
+            Self::Sum(term) => term.expr(fatty_acid, value),
+            Self::Add(formulas) => formulas
+                .iter()
+                .map(|formula| formula.expr(fatty_acid, value.clone()))
+                .reduce(|lhs, rhs| lhs + rhs)
+                .unwrap_or(lit(0.0)),
+            Self::Sub(lhs, rhs) => {
+                lhs.expr(fatty_acid, value.clone()) - rhs.expr(fatty_acid, value)
+            }
+            Self::Mul(formulas) => formulas
+                .iter()
+                .map(|formula| formula.expr(fatty_acid, value.clone()))
+                .reduce(|lhs, rhs| lhs * rhs)
+                .unwrap_or(lit(1.0)),
+            Self::Div(lhs, rhs) => {
+                lhs.expr(fatty_acid, value.clone()) / rhs.expr(fatty_acid, value)
+            }
+            Self::If(condition, then, otherwise) => when(condition.expr(fatty_acid, value.clone()))
+                .then(then.expr(fatty_acid, value.clone()))
+                .otherwise(otherwise.expr(fatty_acid, value)),
+        }
+    }
+}
+
+impl Condition {
+    /// Compile to an expression, the same as [`evaluate`](Self::evaluate).
+    pub fn expr(&self, fatty_acid: &FattyAcidExpr, value: Expr) -> Expr {
+        match self {
+            Self::Compare(lhs, comparison, rhs) => {
+                let lhs = lhs.expr(fatty_acid, value.clone());
+                let rhs = rhs.expr(fatty_acid, value);
+                match comparison {
+                    Comparison::Lt => lhs.lt(rhs),
+                    Comparison::Le => lhs.lt_eq(rhs),
+                    Comparison::Gt => lhs.gt(rhs),
+                    Comparison::Ge => lhs.gt_eq(rhs),
+                    Comparison::Eq => lhs.eq(rhs),
+                    Comparison::Ne => lhs.neq(rhs),
+                }
+            }
+            Self::And(lhs, rhs) => lhs
+                .expr(fatty_acid, value.clone())
+                .and(rhs.expr(fatty_acid, value)),
+            Self::Or(lhs, rhs) => lhs
+                .expr(fatty_acid, value.clone())
+                .or(rhs.expr(fatty_acid, value)),
+            Self::Not(condition) => condition.expr(fatty_acid, value).not(),
+        }
+    }
+}
+
+impl Term {
+    /// Compile to an expression, the same as [`evaluate`](Self::evaluate).
+    pub fn expr(&self, fatty_acid: &FattyAcidExpr, value: Expr) -> Expr {
+        let filtered = match self {
+            Self::Total => value,
+            Self::Sfa => fatty_acid.sfa(value),
+            Self::Ufa => fatty_acid.ufa(value),
+            Self::Mufa => fatty_acid.mufa(value),
+            Self::Pufa => fatty_acid.pufa(value),
+            Self::Enoics(n) => value.filter(fatty_acid.unsaturated().eq(lit(*n as u32))),
+            Self::N(n) => fatty_acid.n(*n, value),
+            Self::PufaN(n) => fatty_acid.pufa_n(*n, value),
+            Self::Tfa(conjugated) => fatty_acid.tfa(*conjugated, value),
+            Self::Bonds => return fatty_acid.ui(value),
+            Self::ChainLength { class, boundaries } => {
+                fatty_acid.chain_length(*class, boundaries, value)
+            }
+            Self::Parity(parity) => value.filter(fatty_acid.is_parity(*parity)),
+            Self::Acid(pattern) => value.filter(fatty_acid.positional(pattern)),
+        };
+        filtered.sum()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        FattyAcid,
+        chain_length::{Boundaries, ChainLength, Parity},
+        composition::Composition,
+        fatty_acid,
+        index::parse::parse,
+        polars::{COLUMN, ExprExt as _, FindByName as _},
+    };
+
+    fn data_frame() -> PolarsResult<DataFrame> {
+        // Unknown isomerism
+        let mut unknown = fatty_acid!(18;9,12);
+        for bond in &mut unknown.unsaturated {
+            bond.isomerism = None;
+        }
+        // Without positions
+        let without = |mut fatty_acid: FattyAcid| {
+            for bond in &mut fatty_acid.unsaturated {
+                bond.index = None;
+                bond.isomerism = None;
+            }
+            fatty_acid
+        };
+        Ok(df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(12),
+                    fatty_acid!(14),
+                    fatty_acid!(16),
+                    fatty_acid!(16;9),
+                    fatty_acid!(18),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;-11),
+                    without(fatty_acid!(18;9)),
+                    fatty_acid!(18;9,12),
+                    unknown,
+                    fatty_acid!(18;9,12,15),
+                    fatty_acid!(20;5,8,11,14,17),
+                    without(fatty_acid!(20;5,8,11,14,17)),
+                    fatty_acid!(22;4,7,10,13,16,19),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [2.0, 8.0, 24.0, 1.0, 12.0, 30.0, 3.0, 4.0, 13.0, 2.0, 2.0, 2.0, 1.0, 3.0],
+        }?)
+    }
+
+    #[test]
+    fn default() -> PolarsResult<()> {
+        let registry = Registry::default();
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let mut exprs = registry.exprs(&fatty_acid, value.clone());
+        exprs.extend([
+            fatty_acid.ia(value.clone()).alias("Filter IA"),
+            fatty_acid.it(value.clone()).alias("Filter IT"),
+            fatty_acid.hh(value.clone()).alias("Filter HH"),
+            fatty_acid.hpi(value.clone()).alias("Filter HPI"),
+            fatty_acid.ui(value.clone()).alias("Filter UI"),
+            fatty_acid.flq(value.clone()).alias("Filter FLQ"),
+            fatty_acid.n6_n3(value).alias("Filter n-6/n-3"),
+        ]);
+        let indices = data_frame()?.lazy().select(exprs).collect()?;
+        let value = |name: &str| -> PolarsResult<_> {
+            Ok(indices[name]
+                .cast(&DataType::Float64)?
+                .f64()?
+                .get(0)
+                .unwrap())
+        };
+        let assert_close = |actual: f64, expected: f64| {
+            assert!((actual - expected).abs() < 1e-12, "{actual} != {expected}");
+        };
+        for name in ["IA", "IT", "HH", "HPI", "UI", "FLQ", "n-6/n-3"] {
+            assert_close(value(name)?, value(&format!("Filter {name}"))?);
+        }
+        // (13 + 2 + 2 + 2 + 1 + 3) / (2 + 8 + 24 + 12)
+        assert_close(value("PUFA/SFA")?, 23.0 / 46.0);
+        // (18:0 + 18:1) / 16:0
+        assert_close(value("NVI")?, 49.0 / 24.0);
+        // (18:1 + 10.3 * 18:2 + 21.6 * 18:3) / 100
+        assert_close(value("COX")?, (37.0 + 10.3 * 15.0 + 21.6 * 2.0) / 100.0);
+        // 0.025 * 38 + 15 + 2 * 2 + 6 * 3 + 8 * 3
+        assert_close(value("PI")?, 0.025 * 38.0 + 15.0 + 4.0 + 18.0 + 24.0);
+        assert_close(value("Δ9 C16")?, 100.0 * 1.0 / 25.0);
+        // The 18:1 without a position is counted as cis-9, the trans-11 is not
+        assert_close(value("Δ9 C18")?, 100.0 * 34.0 / 46.0);
+        Ok(())
+    }
+
+    #[test]
+    fn ron() -> PolarsResult<()> {
+        let custom = Registry::from_ron(
+            r#"[(
+                name: "C18:1/C18:0",
+                description: "Custom",
+                formula: Div(
+                    Sum(Acid((carbons: 18, unsaturated: [()]))),
+                    Sum(Acid((carbons: 18, unsaturated: []))),
+                ),
+            )]"#,
+        )
+        .unwrap();
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let indices = data_frame()?
+            .lazy()
+            .select(custom.exprs(&fatty_acid, col("Value")))
+            .collect()?;
+        assert_eq!(indices["C18:1/C18:0"].f64()?.get(0), Some(37.0 / 12.0));
+        Ok(())
+    }
+
+    #[test]
+    fn chain_length() -> PolarsResult<()> {
+        // C12 and C14 are medium chain with the C8/C16/C22 boundaries
+        let term = Term::ChainLength {
+            class: ChainLength::Medium,
+            boundaries: Boundaries {
+                medium: 8,
+                long: 16,
+                very_long: 22,
+            },
+        };
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let data_frame = data_frame()?;
+        let sums = data_frame
+            .clone()
+            .lazy()
+            .select([
+                term.expr(&fatty_acid, col("Value")).alias("Medium"),
+                Term::Parity(Parity::Even)
+                    .expr(&fatty_acid, col("Value"))
+                    .alias("Even"),
+            ])
+            .collect()?;
+        assert_eq!(sums["Medium"].f64()?.get(0), Some(10.0));
+        assert_eq!(sums["Even"].f64()?.get(0), Some(107.0));
+        let composition = Composition::from_data_frame(&data_frame, "Value")?;
+        assert_eq!(term.evaluate(&composition), 10.0);
+        assert_eq!(Term::Parity(Parity::Odd).evaluate(&composition), 0.0);
+        Ok(())
+    }
+
+    #[test]
+    fn names() -> PolarsResult<()> {
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let data_frame = data_frame()?;
+        let names = [
+            ("OA", fatty_acid.oleic(value.clone())),
+            ("LA", fatty_acid.linoleic(value.clone())),
+            ("ALA", fatty_acid.alpha_linolenic(value.clone())),
+            ("EPA", fatty_acid.eicosapentaenoic(value.clone())),
+            ("DHA", fatty_acid.docosahexaenoic(value.clone())),
+        ];
+        let sums = data_frame
+            .clone()
+            .lazy()
+            .select(
+                names
+                    .iter()
+                    .flat_map(|(name, find)| {
+                        [
+                            parse(name)
+                                .unwrap()
+                                .expr(&fatty_acid, value.clone())
+                                .alias(*name),
+                            find.clone().sum().alias(format!("Find {name}")),
+                        ]
+                    })
+                    .collect::<Vec<_>>(),
+            )
+            .collect()?;
+        let composition = Composition::from_data_frame(&data_frame, "Value")?;
+        for (name, expected) in [
+            ("OA", 34.0),
+            ("LA", 15.0),
+            ("ALA", 2.0),
+            ("EPA", 3.0),
+            ("DHA", 3.0),
+        ] {
+            assert_eq!(sums[name].f64()?.get(0), Some(expected), "{name}");
+            assert_eq!(
+                sums[&*format!("Find {name}")].f64()?.get(0),
+                Some(expected),
+                "{name}"
+            );
+            assert_eq!(
+                parse(name).unwrap().evaluate(&composition),
+                expected,
+                "{name}"
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn formula() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(12),
+                    fatty_acid!(14),
+                    fatty_acid!(16),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;9,12),
+                    fatty_acid!(20;5,8,11,14,17),
+                    fatty_acid!(22;4,7,10,13,16,19),
+                ]
+                .to_vec(),
+            ),
+            "Value" => [2.0, 8.0, 25.0, 40.0, 15.0, 4.0, 6.0],
+        }?;
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let value = col("Value");
+        let formula = |text: &str| parse(text).unwrap().expr(&fatty_acid, value.clone());
+        let registry = Registry::default();
+        let ia = registry.get("IA").unwrap().formula.clone();
+        let data_frame = data_frame
+            .lazy()
+            .select([
+                formula("(C12:0 + 4*C14:0 + C16:0) / UFA").alias("IA"),
+                formula("(EPA + DHA) / SUM").alias("EPA+DHA"),
+                formula("2*c18u1c9 - C18:1n-9 + 18:1Δ9-OA").alias("OA"),
+                formula("PUFA_n3 / n6").alias("n-3/n-6"),
+                formula("MCFA + LCFA + VLCFA - ECFA + OCFA").alias("Chain length"),
+                formula("if n3 >= 10 and not SFA > 50 then 1 else 2").alias("If"),
+                formula("if LA < 1 or UI == 0 then 1 else 2.5").alias("Else"),
+                ia.expr(&fatty_acid, value.clone()).alias("Registry IA"),
+                fatty_acid.ufa(value.clone()).sum().alias("UFA"),
+            ])
+            .collect()?;
+        let value = |name: &str| -> PolarsResult<_> {
+            Ok(data_frame[name]
+                .cast(&DataType::Float64)?
+                .f64()?
+                .get(0)
+                .unwrap())
+        };
+        assert_eq!(value("IA")?, (2.0 + 32.0 + 25.0) / 65.0);
+        assert_eq!(value("EPA+DHA")?, 0.1);
+        assert_eq!(value("OA")?, 40.0);
+        assert_eq!(value("n-3/n-6")?, 10.0 / 15.0);
+        assert_eq!(value("Chain length")?, 0.0);
+        assert_eq!(value("If")?, 1.0);
+        assert_eq!(value("Else")?, 2.5);
+        assert_eq!(value("Registry IA")?, value("IA")?);
+        Ok(())
+    }
+}
@@ -0,0 +1,103 @@
+use super::{DATA_TYPE, SCHEMA};
+use crate::fatty_acid::{FattyAcid, Unsaturated};
+use polars::prelude::*;
+
+impl FattyAcid {
+    /// Literal
+    ///
+    /// A literal [`Expr`] conforming to the fatty acid [`SCHEMA`].
+    pub fn lit(&self) -> Expr {
+        lit(Scalar::from(self))
+    }
+}
+
+impl From<&FattyAcid> for AnyValue<'static> {
+    fn from(value: &FattyAcid) -> Self {
+        let unsaturated = unsaturated(&value.unsaturated)
+            .expect(r#"Expected "Unsaturated" fields of equal length"#);
+        AnyValue::StructOwned(Box::new((
+            vec![AnyValue::UInt8(value.carbons), AnyValue::List(unsaturated)],
+            SCHEMA.iter_fields().collect(),
+        )))
+    }
+}
+
+impl From<&FattyAcid> for Scalar {
+    fn from(value: &FattyAcid) -> Self {
+        Scalar::new(DATA_TYPE.clone(), value.into())
+    }
+}
+
+fn unsaturated(unsaturated: &[Unsaturated]) -> PolarsResult<Series> {
+    let index = Series::new(
+        "Index".into(),
+        unsaturated
+            .iter()
+            .map(|unsaturated| unsaturated.index)
+            .collect::<Vec<_>>(),
+    );
+    let isomerism = Series::new(
+        "Isomerism".into(),
+        unsaturated
+            .iter()
+            .map(|unsaturated| unsaturated.isomerism.map(i8::from))
+            .collect::<Vec<_>>(),
+    );
+    let unsaturation = Series::new(
+        "Unsaturation".into(),
+        unsaturated
+            .iter()
+            .map(|unsaturated| unsaturated.unsaturation.map(u8::from))
+            .collect::<Vec<_>>(),
+    );
+    Ok(StructChunked::from_series(
+        PlSmallStr::EMPTY,
+        unsaturated.len(),
+        [index, isomerism, unsaturation].iter(),
+    )?
+    .into_series())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        fatty_acid,
+        polars::{COLUMN, DataFrameExt as _, ExprExt as _},
+    };
+
+    #[test]
+    fn lit() -> PolarsResult<()> {
+        let fatty_acids = [fatty_acid!(16), fatty_acid!(18;9), fatty_acid!(18;-9,12;15)];
+        let data_frame = DataFrame::empty()
+            .lazy()
+            .select([
+                concat_list(fatty_acids.iter().map(FattyAcid::lit).collect::<Vec<_>>())?
+                    .explode()
+                    .alias(COLUMN),
+            ])
+            .collect()?;
+        assert_eq!(data_frame[COLUMN].dtype(), &*DATA_TYPE);
+        let fatty_acid = data_frame.fatty_acid();
+        for (index, expected) in fatty_acids.iter().enumerate() {
+            assert_eq!(fatty_acid.get(index)?.as_ref(), Some(expected));
+        }
+        // Equality
+        let filtered = data_frame
+            .lazy()
+            .filter(col(COLUMN).eq(fatty_acid!(18;9).lit()))
+            .collect()?;
+        assert_eq!(filtered.height(), 1);
+        assert_eq!(
+            filtered.fatty_acid().get(0)?.as_ref(),
+            Some(&fatty_acid!(18;9)),
+        );
+        // Fields
+        let carbons = DataFrame::empty()
+            .lazy()
+            .select([fatty_acid!(18;9,12).lit().fatty_acid().carbons()])
+            .collect()?;
+        assert_eq!(carbons[0].u8()?.get(0), Some(18));
+        Ok(())
+    }
+}
@@ -1,4 +1,29 @@
-pub use self::{column::ColumnExt, data_frame::DataFrameExt, expr::ExprExt, series::SeriesExt};
+pub use self::{
+    authenticity::{Blend, Metric, blend, distance, mahalanobis, rank},
+    column::ColumnExt,
+    compliance::{Check, Compliance, Limits, Standard, Standards, Status},
+    composition::UNIT,
+    data_frame::DataFrameExt,
+    expr::{
+        ExprExt, FattyAcidExpr,
+        composition::Fraction,
+        filter::Filter,
+        find::{Find, FindByName},
+    },
+    identification::{ECL, PHASE},
+    optimizer::{Constraint, Objective, Optimizer, Optimum},
+    quantification::{InternalStandard, Quantification, ResponseFactors},
+    rollup::{LEVEL, NAME, rollup},
+    schema::{Mismatch, Report, validate_schema},
+    series::SeriesExt,
+};
+pub use crate::fatty_acid::index::{
+    Comparison, Condition, Formula, Index, Registry, Term,
+    parse::{
+        Error as FormulaError, ErrorKind as FormulaErrorKind, parse as parse_formula,
+        parse_with as parse_formula_with,
+    },
+};
 
 use polars::prelude::*;
 use std::sync::LazyLock;
@@ -12,18 +37,25 @@ pub static SCHEMA: LazyLock<Schema> = LazyLock::new(|| {
         Field::new("Carbons".into(), DataType::UInt8),
         Field::new(
             "Unsaturated".into(),
-            DataType::Struct(vec![
-                Field::new("Index".into(), DataType::List(Box::new(DataType::UInt8))),
-                Field::new("Isomerism".into(), DataType::List(Box::new(DataType::Int8))),
-                Field::new(
-                    "Unsaturation".into(),
-                    DataType::List(Box::new(DataType::UInt8)),
-                ),
-            ]),
+            DataType::List(Box::new(DataType::Struct(vec![
+                Field::new("Index".into(), DataType::UInt8),
+                Field::new("Isomerism".into(), DataType::Int8),
+                Field::new("Unsaturation".into(), DataType::UInt8),
+            ]))),
         ),
     ])
 });
 
+/// Fatty acid data type
+pub static DATA_TYPE: LazyLock<DataType> = LazyLock::new(|| {
+    DataType::Struct(
+        SCHEMA
+            .iter()
+            .map(|(name, data_type)| Field::new(name.clone(), data_type.clone()))
+            .collect(),
+    )
+});
+
 /// Extension methods for [`Schema`]
 pub trait SchemaExt {
     fn names(&self) -> Vec<Expr>;
@@ -35,7 +67,17 @@ impl SchemaExt for Schema {
     }
 }
 
+mod authenticity;
 mod column;
+mod compliance;
+mod composition;
 mod data_frame;
 mod expr;
+mod identification;
+mod index;
+mod literal;
+mod optimizer;
+mod quantification;
+mod rollup;
+mod schema;
 mod series;
@@ -0,0 +1,712 @@
+use super::{
+    COLUMN, ExprExt as _, Formula, Registry, Term,
+    authenticity::{align, composition},
+};
+use crate::fatty_acid::composition::Composition;
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Blend optimizer
+///
+/// Finds the proportions of oils, `pᵢ ≥ 0`, `Σpᵢ = 1`, optimizing the
+/// objective within the constraints. A [`Term`] of a blend is the
+/// proportion weighted sum of the terms of the oils, so the problem is a
+/// linear program.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct Optimizer {
+    pub objective: Objective,
+    pub constraints: Vec<Constraint>,
+}
+
+/// Objective
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum Objective {
+    Maximize(Term),
+    Minimize(Term),
+    /// The closest blend to the target composition, in least absolute
+    /// deviations
+    Target(DataFrame),
+}
+
+/// Constraint, % of total fatty acids
+///
+/// Constraints are linear in the proportions: sums of terms and constants,
+/// scaled by constants, or a constant times a ratio of two such formulas.
+/// Other formulas, e.g. the conditional IT or a product of sums, are rejected.
+/// Of the [`Registry::default`] indices, all but the conditional IT are
+/// linear.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum Constraint {
+    /// `min ≤ term ≤ max`
+    Term {
+        term: Term,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// `min ≤ numerator / denominator ≤ max`, e.g. n-6/n-3
+    Ratio {
+        numerator: Term,
+        denominator: Term,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// `min ≤ formula ≤ max`, e.g. the formula of a custom
+    /// [`Index`](super::Index)
+    Formula {
+        formula: Formula,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// `min ≤ index ≤ max` of the [`Registry::default`] index by name, e.g.
+    /// `IA`
+    Index {
+        name: String,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+    /// `min ≤ pᵢ ≤ max` of the oil at the index
+    Proportion {
+        index: usize,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+}
+
+/// Optimal blend
+#[derive(Clone, Debug, PartialEq)]
+pub struct Optimum {
+    /// Proportions of the oils, in order
+    pub proportions: Vec<f64>,
+    /// Blended composition, the fatty acid [`COLUMN`] and the value column
+    /// normalized to 100
+    pub composition: DataFrame,
+}
+
+impl Optimizer {
+    /// Optimize the blend of the oils
+    ///
+    /// Each oil has the fatty acid [`COLUMN`] and the `value` column, the
+    /// compositions are normalized to 100. An oil summing to 0 is an error.
+    pub fn optimize(&self, oils: &[&DataFrame], value: &str) -> PolarsResult<Optimum> {
+        polars_ensure!(!oils.is_empty(), ComputeError: "no oils to blend");
+        for (index, oil) in oils.iter().enumerate() {
+            polars_ensure!(
+                composition(oil, value)?.total() != 0.0,
+                ComputeError: "oil {index} `{value}` sums to 0"
+            );
+        }
+        let count = oils.len();
+        let formulas = |formula: &Formula| -> PolarsResult<Vec<f64>> {
+            oils.iter()
+                .map(|oil| evaluate(formula, oil, value))
+                .collect()
+        };
+        let terms = |term: &Term| formulas(&Formula::Sum(term.clone()));
+        let bound = |program: &mut Program, formula: &Formula, min, max| -> PolarsResult<()> {
+            match ratio(formula) {
+                (numerator, Some(denominator)) => {
+                    program.ratio(&formulas(&numerator)?, &formulas(denominator)?, min, max)
+                }
+                (_, None) => program.bound(formulas(formula)?, min, max),
+            }
+            Ok(())
+        };
+        let registry = Registry::default();
+        let mut program = Program::new(count);
+        program.constrain(vec![1.0; count], Sense::Eq, 1.0);
+        for constraint in &self.constraints {
+            match constraint {
+                Constraint::Term { term, min, max } => program.bound(terms(term)?, *min, *max),
+                Constraint::Ratio {
+                    numerator,
+                    denominator,
+                    min,
+                    max,
+                } => program.ratio(&terms(numerator)?, &terms(denominator)?, *min, *max),
+                Constraint::Formula { formula, min, max } => {
+                    polars_ensure!(
+                        is_linear(formula),
+                        ComputeError: "blend constraint is not linear: {formula:?}"
+                    );
+                    bound(&mut program, formula, *min, *max)?;
+                }
+                Constraint::Index { name, min, max } => {
+                    let Some(index) = registry.get(name) else {
+                        polars_bail!(ComputeError: "unknown blend constraint index `{name}`");
+                    };
+                    polars_ensure!(
+                        is_linear(&index.formula),
+                        ComputeError: "blend constraint index `{name}` is not linear"
+                    );
+                    bound(&mut program, &index.formula, *min, *max)?;
+                }
+                Constraint::Proportion { index, min, max } => {
+                    polars_ensure!(*index < count, OutOfBounds: "oil index {index} out of bounds");
+                    let mut unit = vec![0.0; count];
+                    unit[*index] = 1.0;
+                    program.bound(unit, *min, *max);
+                }
+            }
+        }
+        match &self.objective {
+            Objective::Maximize(term) => program.objective = terms(term)?,
+            Objective::Minimize(term) => {
+                program.objective = terms(term)?.into_iter().map(|term| -term).collect();
+            }
+            Objective::Target(target) => {
+                // `Σpᵢcᵢₖ - tₖ = eₖ⁺ - eₖ⁻`, minimize `Σ(eₖ⁺ + eₖ⁻)`
+                let data_frames = std::iter::once(target)
+                    .chain(oils.iter().copied())
+                    .collect::<Vec<_>>();
+                let aligned = align(&data_frames, value)?;
+                let length = aligned[0].len();
+                program.objective.resize(count + 2 * length, -1.0);
+                for (coefficients, ..) in &mut program.constraints {
+                    coefficients.resize(count + 2 * length, 0.0);
+                }
+                for index in 0..length {
+                    let mut coefficients = vec![0.0; count + 2 * length];
+                    for oil in 0..count {
+                        coefficients[oil] = aligned[oil + 1][index];
+                    }
+                    coefficients[count + 2 * index] = -1.0;
+                    coefficients[count + 2 * index + 1] = 1.0;
+                    program.constrain(coefficients, Sense::Eq, aligned[0][index]);
+                }
+            }
+        }
+        let solution = program.maximize().map_err(|error| match error {
+            Error::Infeasible => polars_err!(ComputeError: "blend constraints are infeasible"),
+            Error::Unbounded => polars_err!(ComputeError: "blend objective is unbounded"),
+        })?;
+        let proportions = solution[..count].to_vec();
+        let composition = blend(oils, &proportions, value)?;
+        Ok(Optimum {
+            proportions,
+            composition,
+        })
+    }
+}
+
+/// Formula of the composition normalized to 100
+fn evaluate(formula: &Formula, oil: &DataFrame, value: &str) -> PolarsResult<f64> {
+    let fatty_acid = col(COLUMN).fatty_acid();
+    let value = col(value).cast(DataType::Float64);
+    let normalized = value.clone() * lit(100.0) / value.sum();
+    let data_frame = oil
+        .clone()
+        .lazy()
+        .select([formula
+            .expr(&fatty_acid, normalized)
+            .cast(DataType::Float64)])
+        .collect()?;
+    Ok(data_frame[0].f64()?.get(0).unwrap_or_default())
+}
+
+/// The formula is affine or a ratio of affine formulas, see [`is_affine`]
+fn is_linear(formula: &Formula) -> bool {
+    let (numerator, denominator) = ratio(formula);
+    is_affine(&numerator) && denominator.is_none_or(is_affine)
+}
+
+/// Numerator and denominator of a ratio formula, `a / b` or `c * a / b` with a
+/// non-constant `b`, else the formula itself
+fn ratio(formula: &Formula) -> (Formula, Option<&Formula>) {
+    match formula {
+        Formula::Div(numerator, denominator) if !is_constant(denominator) => {
+            ((**numerator).clone(), Some(&**denominator))
+        }
+        Formula::Mul(formulas) => {
+            let mut variables = formulas.iter().filter(|formula| !is_constant(formula));
+            match (variables.next(), variables.next()) {
+                (Some(Formula::Div(numerator, denominator)), None) if !is_constant(denominator) => {
+                    let numerator = formulas
+                        .iter()
+                        .map(|formula| match is_constant(formula) {
+                            true => formula.clone(),
+                            false => (**numerator).clone(),
+                        })
+                        .collect();
+                    (Formula::Mul(numerator), Some(&**denominator))
+                }
+                _ => (formula.clone(), None),
+            }
+        }
+        formula => (formula.clone(), None),
+    }
+}
+
+/// The formula holds no terms
+fn is_constant(formula: &Formula) -> bool {
+    match formula {
+        Formula::Constant(_) => true,
+        Formula::Sum(_) | Formula::If(..) => false,
+        Formula::Add(formulas) | Formula::Mul(formulas) => formulas.iter().all(is_constant),
+        Formula::Sub(lhs, rhs) | Formula::Div(lhs, rhs) => is_constant(lhs) && is_constant(rhs),
+    }
+}
+
+/// The formula is a sum of terms and constants, scaled by constants
+///
+/// The formula of a blend is then the proportion weighted sum of the formulas
+/// of the oils, as `Σpᵢ = 1`.
+fn is_affine(formula: &Formula) -> bool {
+    match formula {
+        Formula::Constant(_) | Formula::Sum(_) => true,
+        Formula::Add(formulas) => formulas.iter().all(is_affine),
+        Formula::Sub(lhs, rhs) => is_affine(lhs) && is_affine(rhs),
+        Formula::Mul(formulas) => {
+            formulas.iter().all(is_affine)
+                && formulas
+                    .iter()
+                    .filter(|formula| !is_constant(formula))
+                    .count()
+                    <= 1
+        }
+        Formula::Div(lhs, rhs) => is_affine(lhs) && is_constant(rhs),
+        Formula::If(..) => false,
+    }
+}
+
+fn blend(oils: &[&DataFrame], proportions: &[f64], value: &str) -> PolarsResult<DataFrame> {
+    let mut blended = Composition::default();
+    for (oil, proportion) in oils.iter().zip(proportions) {
+        if *proportion <= 0.0 {
+            continue;
+        }
+        let composition = composition(oil, value)?.normalized();
+        for (fatty_acid, value) in composition.iter() {
+            let sum = blended.get(fatty_acid).unwrap_or_default() + proportion * value;
+            blended.insert(fatty_acid.clone(), sum);
+        }
+    }
+    DataFrame::new(vec![
+        Series::new(
+            COLUMN.into(),
+            blended
+                .iter()
+                .map(|(fatty_acid, _)| fatty_acid.clone())
+                .collect::<Vec<_>>(),
+        )
+        .into(),
+        Series::new(
+            value.into(),
+            blended.iter().map(|(_, value)| value).collect::<Vec<_>>(),
+        )
+        .into(),
+    ])
+}
+
+/// Linear program, maximize `cᵀx` subject to the constraints and `x ≥ 0`
+struct Program {
+    objective: Vec<f64>,
+    constraints: Vec<(Vec<f64>, Sense, f64)>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Sense {
+    Le,
+    Ge,
+    Eq,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Error {
+    Infeasible,
+    Unbounded,
+}
+
+const EPSILON: f64 = 1e-9;
+
+impl Program {
+    fn new(variables: usize) -> Self {
+        Self {
+            objective: vec![0.0; variables],
+            constraints: Vec::new(),
+        }
+    }
+
+    fn constrain(&mut self, coefficients: Vec<f64>, sense: Sense, rhs: f64) {
+        self.constraints.push((coefficients, sense, rhs));
+    }
+
+    /// `min ≤ cᵀx ≤ max`
+    fn bound(&mut self, coefficients: Vec<f64>, min: Option<f64>, max: Option<f64>) {
+        if let Some(min) = min {
+            self.constrain(coefficients.clone(), Sense::Ge, min);
+        }
+        if let Some(max) = max {
+            self.constrain(coefficients, Sense::Le, max);
+        }
+    }
+
+    /// `min ≤ nᵀx / dᵀx ≤ max` as `nᵀx - r dᵀx`, for a positive `dᵀx`
+    fn ratio(
+        &mut self,
+        numerator: &[f64],
+        denominator: &[f64],
+        min: Option<f64>,
+        max: Option<f64>,
+    ) {
+        let linear = |ratio: f64| {
+            numerator
+                .iter()
+                .zip(denominator)
+                .map(|(numerator, denominator)| numerator - ratio * denominator)
+                .collect::<Vec<_>>()
+        };
+        if let Some(min) = min {
+            self.constrain(linear(min), Sense::Ge, 0.0);
+        }
+        if let Some(max) = max {
+            self.constrain(linear(max), Sense::Le, 0.0);
+        }
+    }
+
+    /// Two phase simplex with Bland's rule
+    fn maximize(&self) -> Result<Vec<f64>, Error> {
+        let variables = self.objective.len();
+        let rows = self.constraints.len();
+        let slacks = self
+            .constraints
+            .iter()
+            .filter(|(_, sense, _)| *sense != Sense::Eq)
+            .count();
+        // Every row gets an artificial variable, the feasible basis of the
+        // first phase
+        let artificial = variables + slacks;
+        let columns = artificial + rows;
+        let mut tableau = Tableau {
+            rows: Vec::with_capacity(rows),
+            basis: Vec::with_capacity(rows),
+        };
+        let mut slack = variables;
+        for (row, (coefficients, sense, rhs)) in self.constraints.iter().enumerate() {
+            let sign = if *rhs < 0.0 { -1.0 } else { 1.0 };
+            let mut values = vec![0.0; columns + 1];
+            for (value, coefficient) in values.iter_mut().zip(coefficients) {
+                *value = sign * coefficient;
+            }
+            match sense {
+                Sense::Le => values[slack] = sign,
+                Sense::Ge => values[slack] = -sign,
+                Sense::Eq => {}
+            }
+            if *sense != Sense::Eq {
+                slack += 1;
+            }
+            values[artificial + row] = 1.0;
+            values[columns] = sign * rhs;
+            tableau.rows.push(values);
+            tableau.basis.push(artificial + row);
+        }
+        // Phase 1, minimize the sum of the artificial variables
+        let mut objective = vec![0.0; columns];
+        objective[artificial..].fill(-1.0);
+        tableau.optimize(&objective, columns)?;
+        if tableau.value(&objective) < -EPSILON * (1.0 + tableau.scale()) {
+            return Err(Error::Infeasible);
+        }
+        // Drive the artificial variables out of the basis
+        for row in 0..rows {
+            if tableau.basis[row] < artificial {
+                continue;
+            }
+            let column = (0..artificial).find(|&column| tableau.rows[row][column].abs() > EPSILON);
+            if let Some(column) = column {
+                tableau.pivot(row, column);
+            }
+        }
+        // Phase 2
+        let mut objective = self.objective.clone();
+        objective.resize(columns, 0.0);
+        tableau.optimize(&objective, artificial)?;
+        let mut solution = vec![0.0; variables];
+        for (row, &basis) in tableau.basis.iter().enumerate() {
+            if basis < variables {
+                solution[basis] = tableau.rows[row][columns].max(0.0);
+            }
+        }
+        Ok(solution)
+    }
+}
+
+struct Tableau {
+    /// Rows of coefficients and the right hand side last
+    rows: Vec<Vec<f64>>,
+    basis: Vec<usize>,
+}
+
+impl Tableau {
+    /// Maximize, only the columns before `entering` may enter the basis
+    fn optimize(&mut self, objective: &[f64], entering: usize) -> Result<(), Error> {
+        let rhs = objective.len();
+        loop {
+            let reduced = |column: usize| {
+                objective[column]
+                    - self
+                        .rows
+                        .iter()
+                        .zip(&self.basis)
+                        .map(|(row, &basis)| objective[basis] * row[column])
+                        .sum::<f64>()
+            };
+            let Some(column) = (0..entering)
+                .find(|&column| !self.basis.contains(&column) && reduced(column) > EPSILON)
+            else {
+                return Ok(());
+            };
+            let mut leaving = None::<(usize, f64)>;
+            for (row, values) in self.rows.iter().enumerate() {
+                if values[column] > EPSILON {
+                    let ratio = values[rhs] / values[column];
+                    let better = match leaving {
+                        None => true,
+                        Some((other, minimum)) => {
+                            ratio < minimum - EPSILON
+                                || (ratio < minimum + EPSILON
+                                    && self.basis[row] < self.basis[other])
+                        }
+                    };
+                    if better {
+                        leaving = Some((row, ratio));
+                    }
+                }
+            }
+            let (row, _) = leaving.ok_or(Error::Unbounded)?;
+            self.pivot(row, column);
+        }
+    }
+
+    fn pivot(&mut self, row: usize, column: usize) {
+        let pivot = self.rows[row][column];
+        self.rows[row].iter_mut().for_each(|value| *value /= pivot);
+        let pivot_row = self.rows[row].clone();
+        for (other, values) in self.rows.iter_mut().enumerate() {
+            let factor = values[column];
+            if other != row && factor != 0.0 {
+                for (value, pivot) in values.iter_mut().zip(&pivot_row) {
+                    *value -= factor * pivot;
+                }
+            }
+        }
+        self.basis[row] = column;
+    }
+
+    fn value(&self, objective: &[f64]) -> f64 {
+        let rhs = objective.len();
+        self.rows
+            .iter()
+            .zip(&self.basis)
+            .map(|(row, &basis)| objective[basis] * row[rhs])
+            .sum()
+    }
+
+    fn scale(&self) -> f64 {
+        self.rows
+            .iter()
+            .map(|row| row[row.len() - 1].abs())
+            .fold(0.0, f64::max)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{FattyAcid, fatty_acid};
+
+    fn oils() -> [DataFrame; 3] {
+        let oil = |fatty_acids: Vec<FattyAcid>, values: &[f64]| {
+            df! {
+                COLUMN => Series::new(PlSmallStr::EMPTY, fatty_acids),
+                "Value" => values,
+            }
+            .unwrap()
+        };
+        [
+            oil(
+                vec![fatty_acid!(16), fatty_acid!(18;9,12,15)],
+                &[30.0, 70.0],
+            ),
+            oil(vec![fatty_acid!(16), fatty_acid!(18;9,12)], &[10.0, 90.0]),
+            oil(vec![fatty_acid!(16), fatty_acid!(18;9)], &[5.0, 95.0]),
+        ]
+    }
+
+    fn assert_proportions(optimum: &Optimum, expected: [f64; 3]) {
+        for (proportion, expected) in optimum.proportions.iter().zip(expected) {
+            assert!((proportion - expected).abs() < 1e-9, "{optimum:?}");
+        }
+    }
+
+    #[test]
+    fn maximize() -> PolarsResult<()> {
+        let oils = oils();
+        let oils = oils.iter().collect::<Vec<_>>();
+        // Maximize n-3 subject to SFA ≤ 20 and n-6/n-3 ≤ 5
+        let mut optimizer = Optimizer {
+            objective: Objective::Maximize(Term::N(3)),
+            constraints: vec![
+                Constraint::Term {
+                    term: Term::Sfa,
+                    min: None,
+                    max: Some(20.0),
+                },
+                Constraint::Ratio {
+                    numerator: Term::N(6),
+                    denominator: Term::N(3),
+                    min: None,
+                    max: Some(5.0),
+                },
+            ],
+        };
+        // `30p₁ + 5(1 - p₁) = 20`
+        let optimum = optimizer.optimize(&oils, "Value")?;
+        assert_proportions(&optimum, [0.6, 0.0, 0.4]);
+        assert_eq!(optimum.composition.height(), 3);
+        let values = optimum.composition["Value"].f64()?;
+        assert!((values.sum().unwrap() - 100.0).abs() < 1e-9);
+        assert!((values.get(1).unwrap() - 42.0).abs() < 1e-9);
+        // n-6/n-3 ≥ 1: `90p₂ ≥ 70p₁` and `25p₁ + 5p₂ ≤ 15`
+        optimizer.constraints.push(Constraint::Ratio {
+            numerator: Term::N(6),
+            denominator: Term::N(3),
+            min: Some(1.0),
+            max: None,
+        });
+        let optimum = optimizer.optimize(&oils, "Value")?;
+        let first = 135.0 / 260.0;
+        assert_proportions(
+            &optimum,
+            [first, first * 7.0 / 9.0, 1.0 - first * 16.0 / 9.0],
+        );
+        // Infeasible
+        optimizer.constraints.push(Constraint::Proportion {
+            index: 2,
+            min: Some(0.5),
+            max: None,
+        });
+        optimizer.constraints.push(Constraint::Term {
+            term: Term::Sfa,
+            min: Some(19.0),
+            max: None,
+        });
+        assert!(optimizer.optimize(&oils, "Value").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn target() -> PolarsResult<()> {
+        let oils = oils();
+        let oils = oils.iter().collect::<Vec<_>>();
+        let target = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(16), fatty_acid!(18;9,12,15), fatty_acid!(18;9,12)],
+            ),
+            "Value" => [20.0, 35.0, 45.0],
+        }?;
+        let optimizer = Optimizer {
+            objective: Objective::Target(target),
+            constraints: Vec::new(),
+        };
+        assert_proportions(&optimizer.optimize(&oils, "Value")?, [0.5, 0.5, 0.0]);
+        let optimizer = Optimizer {
+            objective: Objective::Minimize(Term::Sfa),
+            constraints: Vec::new(),
+        };
+        assert_proportions(&optimizer.optimize(&oils, "Value")?, [0.0, 0.0, 1.0]);
+        Ok(())
+    }
+
+    #[test]
+    fn formula() -> PolarsResult<()> {
+        let oils = oils();
+        let oils = oils.iter().collect::<Vec<_>>();
+        let registry = Registry::default();
+        let formula = |name: &str| registry.get(name).unwrap().formula.clone();
+        // The maximize constraints as formulas
+        let mut optimizer = Optimizer {
+            objective: Objective::Maximize(Term::N(3)),
+            constraints: vec![
+                Constraint::Formula {
+                    formula: "2 * SFA + 10".parse().unwrap(),
+                    min: None,
+                    max: Some(50.0),
+                },
+                Constraint::Formula {
+                    formula: formula("n-6/n-3"),
+                    min: None,
+                    max: Some(5.0),
+                },
+            ],
+        };
+        assert_proportions(&optimizer.optimize(&oils, "Value")?, [0.6, 0.0, 0.4]);
+        // Not linear
+        for formula in [formula("IT"), "SFA * PUFA".parse().unwrap()] {
+            optimizer.constraints.push(Constraint::Formula {
+                formula,
+                min: None,
+                max: Some(1.0),
+            });
+            let error = optimizer.optimize(&oils, "Value").unwrap_err();
+            assert!(error.to_string().contains("not linear"), "{error}");
+            optimizer.constraints.pop();
+        }
+        // Registry indices by name
+        let index = |name: &str, max| Constraint::Index {
+            name: name.to_owned(),
+            min: None,
+            max: Some(max),
+        };
+        optimizer.constraints[1] = index("n-6/n-3", 5.0);
+        optimizer.constraints.push(index("FLQ", 100.0));
+        assert_proportions(&optimizer.optimize(&oils, "Value")?, [0.6, 0.0, 0.4]);
+        for (name, message) in [("IT", "not linear"), ("Unknown", "unknown")] {
+            optimizer.constraints.push(index(name, 1.0));
+            let error = optimizer.optimize(&oils, "Value").unwrap_err();
+            assert!(error.to_string().contains(message), "{error}");
+            optimizer.constraints.pop();
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn zero() -> PolarsResult<()> {
+        let [first, _, third] = oils();
+        let zero = df! {
+            COLUMN => Series::new(PlSmallStr::EMPTY, vec![fatty_acid!(16)]),
+            "Value" => [0.0],
+        }?;
+        let optimizer = Optimizer {
+            objective: Objective::Maximize(Term::N(3)),
+            constraints: Vec::new(),
+        };
+        assert!(optimizer.optimize(&[&first, &zero], "Value").is_err());
+        let optimizer = Optimizer {
+            objective: Objective::Target(zero),
+            constraints: Vec::new(),
+        };
+        assert!(optimizer.optimize(&[&first, &third], "Value").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn ron() {
+        let optimizer = Optimizer {
+            objective: Objective::Minimize(Term::Sfa),
+            constraints: vec![Constraint::Proportion {
+                index: 0,
+                min: Some(0.1),
+                max: None,
+            }],
+        };
+        let text = ron::to_string(&optimizer).unwrap();
+        let deserialized = ron::from_str::<Optimizer>(&text).unwrap();
+        assert!(matches!(
+            deserialized.objective,
+            Objective::Minimize(Term::Sfa)
+        ));
+        assert_eq!(deserialized.constraints, optimizer.constraints);
+    }
+}
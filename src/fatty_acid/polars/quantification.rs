@@ -0,0 +1,167 @@
+use super::{COLUMN, ExprExt as _};
+use crate::fatty_acid::{FattyAcid, derivative::Derivative};
+use polars::prelude::*;
+use serde::{Deserialize, Serialize};
+
+/// Internal standard
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct InternalStandard {
+    /// Fatty acid of the standard peak, e.g. C17:0, C19:0 or C23:0
+    pub fatty_acid: FattyAcid,
+    /// Form of the added standard
+    pub derivative: Derivative,
+    /// Added mass, mg
+    pub mass: f64,
+}
+
+/// Response factors
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum ResponseFactors {
+    /// All response factors are 1
+    #[default]
+    Unit,
+    /// Theoretical FID response factors relative to the internal standard,
+    /// see [`FattyAcid::response_factor`]
+    Theoretical,
+    /// Response factors relative to the internal standard in a column of the
+    /// peak table
+    Column(String),
+}
+
+/// Internal standard quantification (AOCS Ce 1h, AOAC 996.06)
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Quantification {
+    pub standard: InternalStandard,
+    /// Analysed derivative, usually FAME
+    pub derivative: Derivative,
+    /// Sample mass, g
+    pub sample: f64,
+    pub response_factors: ResponseFactors,
+}
+
+impl Quantification {
+    /// Quantify the peak table
+    ///
+    /// The peak table has the fatty acid [`COLUMN`] and the `area` column.
+    /// Returns the fatty acids without the internal standard peak, with:
+    /// - `Derivative`, mg of the analysed derivative,
+    ///   `W = A * R * Wis / Ais`,
+    /// - `Mass`, mg of fatty acid, converted by `M(FA) / M(derivative)`,
+    /// - `Triacylglycerol`, mg of triacylglycerol equivalents, converted by
+    ///   `M(TAG) / 3 / M(derivative)`,
+    /// - `Sample`, mg of fatty acid per g of sample,
+    /// - `Fat`, mg of fatty acid per g of fat, where fat is the sum of the
+    ///   triacylglycerol equivalents.
+    pub fn quantify(&self, peaks: &DataFrame, area: &str) -> PolarsResult<DataFrame> {
+        let fatty_acid = col(COLUMN).fatty_acid();
+        let standard = fatty_acid.matches(&self.standard.fatty_acid);
+        let standard_area = peaks
+            .clone()
+            .lazy()
+            .select([col(area)
+                .filter(standard.clone())
+                .sum()
+                .cast(DataType::Float64)])
+            .collect()?[0]
+            .f64()?
+            .get(0)
+            .unwrap_or_default();
+        polars_ensure!(
+            standard_area > 0.0,
+            ComputeError: "internal standard peak is missing",
+        );
+        // Standard mass as the analysed derivative
+        let standard_mass = self.standard.mass * self.standard.fatty_acid.mass(self.derivative)
+            / self.standard.fatty_acid.mass(self.standard.derivative);
+        let response_factor = match &self.response_factors {
+            ResponseFactors::Unit => lit(1.0),
+            ResponseFactors::Theoretical => {
+                fatty_acid.response_factor(self.derivative, &self.standard.fatty_acid)
+            }
+            ResponseFactors::Column(name) => col(name.as_str()),
+        };
+        let derivative_mass = fatty_acid.derivative_mass(self.derivative);
+        peaks
+            .clone()
+            .lazy()
+            .filter(standard.not())
+            .select([
+                col(COLUMN),
+                (col(area) * response_factor * lit(standard_mass / standard_area))
+                    .alias("Derivative"),
+            ])
+            .with_columns([
+                (col("Derivative") * fatty_acid.derivative_mass(Derivative::Acid)
+                    / derivative_mass.clone())
+                .alias("Mass"),
+                (col("Derivative") * fatty_acid.derivative_mass(Derivative::Triacylglycerol)
+                    / derivative_mass)
+                    .alias("Triacylglycerol"),
+            ])
+            .with_columns([
+                (col("Mass") / lit(self.sample)).alias("Sample"),
+                (col("Mass") / (col("Triacylglycerol").sum() / lit(1000))).alias("Fat"),
+            ])
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn quantify() -> PolarsResult<()> {
+        let peaks = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                vec![fatty_acid!(16), fatty_acid!(17), fatty_acid!(18;9)],
+            ),
+            "Area" => [200.0, 100.0, 300.0],
+            "Factor" => [1.0, 1.0, 2.0],
+        }?;
+        let mut quantification = Quantification {
+            standard: InternalStandard {
+                fatty_acid: fatty_acid!(17),
+                derivative: Derivative::Triacylglycerol,
+                mass: 10.0,
+            },
+            derivative: Derivative::Methyl,
+            sample: 0.5,
+            response_factors: ResponseFactors::Unit,
+        };
+        let quantified = quantification.quantify(&peaks, "Area")?;
+        assert_eq!(quantified.height(), 2);
+        // The standard as FAME
+        let fame = |fatty_acid: FattyAcid, derivative| {
+            fatty_acid.mass(Derivative::Methyl) / fatty_acid.mass(derivative)
+        };
+        let standard = 10.0 * fame(fatty_acid!(17), Derivative::Triacylglycerol);
+        let c16u0 = 2.0 * standard;
+        let c18u1 = 3.0 * standard;
+        let values = |name: &str| -> PolarsResult<Vec<f64>> {
+            Ok(quantified[name].f64()?.iter().flatten().collect())
+        };
+        let assert_close = |actual: Vec<f64>, expected: [f64; 2]| {
+            for (actual, expected) in actual.into_iter().zip(expected) {
+                assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+            }
+        };
+        assert_close(values("Derivative")?, [c16u0, c18u1]);
+        let c16u0 = c16u0 / fame(fatty_acid!(16), Derivative::Acid);
+        let c18u1 = c18u1 / fame(fatty_acid!(18;9), Derivative::Acid);
+        assert_close(values("Mass")?, [c16u0, c18u1]);
+        assert_close(values("Sample")?, [2.0 * c16u0, 2.0 * c18u1]);
+        let fat = values("Triacylglycerol")?.iter().sum::<f64>() / 1000.0;
+        assert_close(values("Fat")?, [c16u0 / fat, c18u1 / fat]);
+        // Response factors
+        quantification.response_factors = ResponseFactors::Column("Factor".to_owned());
+        let quantified = quantification.quantify(&peaks, "Area")?;
+        assert!((quantified["Derivative"].f64()?.get(1).unwrap() - 6.0 * standard).abs() < 1e-9);
+        // Missing standard
+        quantification.standard.fatty_acid = fatty_acid!(23);
+        assert!(quantification.quantify(&peaks, "Area").is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,224 @@
+use super::{COLUMN, ExprExt as _, Term};
+use crate::fatty_acid::{
+    chain_length::{Boundaries, ChainLength, Parity},
+    display::{COMMON, Options},
+};
+use polars::prelude::*;
+
+/// Rollup level column name
+pub const LEVEL: &str = "Level";
+/// Rollup name column name
+pub const NAME: &str = "Name";
+
+/// Class sums of the rollup
+fn classes(boundaries: &Boundaries) -> [(&'static str, Term); 11] {
+    let chain_length = |class| Term::ChainLength {
+        class,
+        boundaries: *boundaries,
+    };
+    [
+        ("SFA", Term::Sfa),
+        ("MUFA", Term::Mufa),
+        ("PUFA", Term::Pufa),
+        ("n-3", Term::N(3)),
+        ("n-6", Term::N(6)),
+        ("TFA", Term::Tfa(false)),
+        ("SCFA", chain_length(ChainLength::Short)),
+        ("MCFA", chain_length(ChainLength::Medium)),
+        ("LCFA", chain_length(ChainLength::Long)),
+        ("VLCFA", chain_length(ChainLength::VeryLong)),
+        ("OCFA", Term::Parity(Parity::Odd)),
+    ]
+}
+
+/// Hierarchical rollup of the `values` columns
+///
+/// Returns a [`LEVEL`], [`NAME`] and `values` table, with the rows of each
+/// level in order:
+/// - `Species`, each fatty acid in input order,
+/// - `C:U`, subtotals by carbons and unsaturated bonds (all 18:1 isomers),
+/// - `Class`, subtotals by class (SFA, MUFA, PUFA, n-3, n-6, TFA, chain
+///   length classes by `boundaries`), a fatty acid may count in several
+///   classes,
+/// - `Total`, the grand total.
+pub fn rollup(
+    data_frame: &DataFrame,
+    values: &[&str],
+    boundaries: &Boundaries,
+) -> PolarsResult<DataFrame> {
+    let fatty_acid = col(COLUMN).fatty_acid();
+    let values = values
+        .iter()
+        .map(|&name| col(name).cast(DataType::Float64))
+        .collect::<Vec<_>>();
+    let level = |level: &str| lit(level).alias(LEVEL);
+    let lazy_frame = data_frame.clone().lazy().filter(col(COLUMN).is_not_null());
+    // Species
+    let mut rollup = lazy_frame
+        .clone()
+        .select(
+            [
+                level("Species"),
+                fatty_acid
+                    .format(Options {
+                        alternate: true,
+                        ..COMMON
+                    })
+                    .alias(NAME),
+            ]
+            .into_iter()
+            .chain(values.iter().cloned())
+            .collect::<Vec<_>>(),
+        )
+        .collect()?;
+    // C:U
+    let subtotals = lazy_frame
+        .clone()
+        .group_by([
+            fatty_acid.carbons().alias("Carbons"),
+            fatty_acid.unsaturated().alias("Unsaturated"),
+        ])
+        .agg(
+            values
+                .iter()
+                .map(|value| value.clone().sum())
+                .collect::<Vec<_>>(),
+        )
+        .sort(["Carbons", "Unsaturated"], Default::default())
+        .collect()?;
+    let names = subtotals["Carbons"]
+        .u8()?
+        .iter()
+        .zip(subtotals["Unsaturated"].u32()?)
+        .map(|(carbons, unsaturated)| Some(format!("{}:{}", carbons?, unsaturated?)))
+        .collect::<StringChunked>()
+        .with_name(NAME.into());
+    let mut columns = vec![
+        Column::new_scalar(
+            LEVEL.into(),
+            Scalar::from(PlSmallStr::from("C:U")),
+            names.len(),
+        ),
+        names.into_column(),
+    ];
+    columns.extend(subtotals.get_columns()[2..].iter().cloned());
+    rollup.vstack_mut(&DataFrame::new(columns)?)?;
+    // Class
+    for (name, term) in classes(boundaries) {
+        let class = lazy_frame
+            .clone()
+            .select(
+                [level("Class"), lit(name).alias(NAME)]
+                    .into_iter()
+                    .chain(
+                        values
+                            .iter()
+                            .map(|value| term.expr(&fatty_acid, value.clone())),
+                    )
+                    .collect::<Vec<_>>(),
+            )
+            .collect()?;
+        rollup.vstack_mut(&class)?;
+    }
+    // Total
+    let total = lazy_frame
+        .select(
+            [level("Total"), lit("Σ").alias(NAME)]
+                .into_iter()
+                .chain(values.iter().map(|value| value.clone().sum()))
+                .collect::<Vec<_>>(),
+        )
+        .collect()?;
+    rollup.vstack_mut(&total)?;
+    rollup.as_single_chunk();
+    Ok(rollup)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn rollup() -> PolarsResult<()> {
+        let data_frame = df! {
+            COLUMN => Series::new(
+                PlSmallStr::EMPTY,
+                [
+                    fatty_acid!(16),
+                    fatty_acid!(18;9),
+                    fatty_acid!(18;-11),
+                    fatty_acid!(18;9,12),
+                    fatty_acid!(18;9,12,15),
+                    fatty_acid!(20;5,8,11,14,17),
+                ]
+                .to_vec(),
+            ),
+            "Mass" => [20.0, 30.0, 5.0, 25.0, 10.0, 10.0],
+            "Area" => [21u32, 29, 5, 25, 10, 10],
+        }?;
+        let boundaries = Boundaries::default();
+        let rollup = super::rollup(&data_frame, &["Mass", "Area"], &boundaries)?;
+        assert_eq!(rollup.width(), 4);
+        assert_eq!(rollup.height(), 6 + 5 + classes(&boundaries).len() + 1);
+        let rows = |level: &str| -> PolarsResult<Vec<(String, f64)>> {
+            let rows = rollup
+                .clone()
+                .lazy()
+                .filter(col(LEVEL).eq(lit(level)))
+                .collect()?;
+            Ok(rows[NAME]
+                .str()?
+                .iter()
+                .zip(rows["Mass"].f64()?)
+                .map(|(name, mass)| (name.unwrap().to_owned(), mass.unwrap()))
+                .collect())
+        };
+        let row = |name: &str, mass: f64| (name.to_owned(), mass);
+        assert_eq!(
+            rows("Species")?,
+            [
+                row("16:0", 20.0),
+                row("18:1Δ9", 30.0),
+                row("18:1Δ11t", 5.0),
+                row("18:2Δ9,12", 25.0),
+                row("18:3Δ9,12,15", 10.0),
+                row("20:5Δ5,8,11,14,17", 10.0),
+            ],
+        );
+        assert_eq!(
+            rows("C:U")?,
+            [
+                row("16:0", 20.0),
+                row("18:1", 35.0),
+                row("18:2", 25.0),
+                row("18:3", 10.0),
+                row("20:5", 10.0),
+            ],
+        );
+        let classes = rows("Class")?;
+        assert_eq!(classes[0], row("SFA", 20.0));
+        assert_eq!(classes[1], row("MUFA", 35.0));
+        assert_eq!(classes[2], row("PUFA", 45.0));
+        assert_eq!(classes[3], row("n-3", 20.0));
+        assert_eq!(classes[4], row("n-6", 25.0));
+        assert_eq!(classes[5], row("TFA", 5.0));
+        assert_eq!(classes[8], row("LCFA", 100.0));
+        assert_eq!(rows("Total")?, [row("Σ", 100.0)]);
+        assert_eq!(rollup["Area"].f64()?.get(6), Some(21.0));
+        // C16 is medium chain with the C8/C17/C22 boundaries
+        let boundaries = Boundaries {
+            medium: 8,
+            long: 17,
+            very_long: 22,
+        };
+        let rollup = super::rollup(&data_frame, &["Mass"], &boundaries)?;
+        let classes = rollup
+            .lazy()
+            .filter(col(LEVEL).eq(lit("Class")))
+            .collect()?;
+        assert_eq!(classes["Mass"].f64()?.get(7), Some(20.0));
+        assert_eq!(classes["Mass"].f64()?.get(8), Some(80.0));
+        Ok(())
+    }
+}
@@ -0,0 +1,377 @@
+use super::{COLUMN, DATA_TYPE};
+use polars::prelude::*;
+use std::fmt::{self, Formatter};
+
+const DOUBLES: &str = "Doubles";
+const TRIPLES: &str = "Triples";
+
+/// Validate the fatty acid [`COLUMN`] of the data frame against
+/// [`SCHEMA`](super::SCHEMA)
+pub fn validate_schema(data_frame: &DataFrame) -> Report {
+    let mut mismatches = Vec::new();
+    match data_frame.column(COLUMN) {
+        Ok(column) => {
+            if is_legacy(column.dtype()) {
+                mismatches.push(Mismatch::Legacy);
+            }
+            compare(COLUMN, &DATA_TYPE, column.dtype(), &mut mismatches);
+        }
+        Err(_) => mismatches.push(Mismatch::MissingColumn),
+    }
+    Report { mismatches }
+}
+
+/// Schema conformance report
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Report {
+    pub mismatches: Vec<Mismatch>,
+}
+
+impl Report {
+    /// The data frame conforms to the schema.
+    pub fn is_valid(&self) -> bool {
+        self.mismatches.is_empty()
+    }
+
+    /// All mismatches can be fixed by [`coerce`](Self::coerce).
+    pub fn is_coercible(&self) -> bool {
+        self.mismatches.iter().all(Mismatch::is_coercible)
+    }
+
+    /// Coerce
+    ///
+    /// Casts compatible data types, drops unexpected fields and migrates the
+    /// legacy `Doubles`/`Triples` layout, where each list holds signed bond
+    /// indices (a negative index is a trans bond).
+    pub fn coerce(&self, data_frame: DataFrame) -> PolarsResult<DataFrame> {
+        if self.is_valid() {
+            return Ok(data_frame);
+        }
+        if let Some(mismatch) = self
+            .mismatches
+            .iter()
+            .find(|mismatch| !mismatch.is_coercible())
+        {
+            polars_bail!(SchemaMismatch: "{mismatch}");
+        }
+        let fatty_acid = col(COLUMN);
+        let carbons = fatty_acid
+            .clone()
+            .struct_()
+            .field_by_name("Carbons")
+            .strict_cast(DataType::UInt8);
+        let unsaturated = if self.mismatches.contains(&Mismatch::Legacy) {
+            // Either of the legacy fields may be absent
+            let DataType::Struct(fields) = data_frame.column(COLUMN)?.dtype() else {
+                polars_bail!(SchemaMismatch: "{}", Mismatch::Legacy);
+            };
+            concat_list(
+                [(DOUBLES, 1), (TRIPLES, 2)]
+                    .into_iter()
+                    .filter(|(name, _)| fields.iter().any(|field| field.name == *name))
+                    .map(|(name, unsaturation)| legacy(fatty_acid.clone(), name, unsaturation))
+                    .collect::<Vec<_>>(),
+            )?
+        } else {
+            fatty_acid
+                .clone()
+                .struct_()
+                .field_by_name("Unsaturated")
+                .list()
+                .eval(
+                    as_struct(vec![
+                        field("Index", DataType::UInt8),
+                        field("Isomerism", DataType::Int8),
+                        field("Unsaturation", DataType::UInt8),
+                    ]),
+                    true,
+                )
+        };
+        data_frame
+            .lazy()
+            .with_column(
+                when(fatty_acid.clone().is_null())
+                    .then(lit(NULL))
+                    .otherwise(as_struct(vec![
+                        carbons.alias("Carbons"),
+                        unsaturated.alias("Unsaturated"),
+                    ]))
+                    .strict_cast(DATA_TYPE.clone())
+                    .alias(COLUMN),
+            )
+            .collect()
+    }
+}
+
+impl fmt::Display for Report {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        for mismatch in &self.mismatches {
+            writeln!(f, "{mismatch}")?;
+        }
+        Ok(())
+    }
+}
+
+/// Schema mismatch
+#[derive(Clone, Debug, PartialEq)]
+pub enum Mismatch {
+    /// The fatty acid column is missing.
+    MissingColumn,
+    /// A field of the schema is missing.
+    MissingField { path: String, expected: DataType },
+    /// A field has a different data type.
+    DataType {
+        path: String,
+        expected: DataType,
+        actual: DataType,
+    },
+    /// A field is not part of the schema.
+    UnexpectedField { path: String, actual: DataType },
+    /// The legacy `Doubles`/`Triples` layout.
+    Legacy,
+}
+
+impl Mismatch {
+    /// The mismatch can be fixed by [`Report::coerce`].
+    pub fn is_coercible(&self) -> bool {
+        match self {
+            Self::MissingColumn | Self::MissingField { .. } => false,
+            Self::DataType {
+                expected, actual, ..
+            } => expected.is_integer() && actual.is_integer(),
+            Self::UnexpectedField { .. } | Self::Legacy => true,
+        }
+    }
+}
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Self::MissingColumn => write!(f, r#"missing "{COLUMN}" column"#),
+            Self::MissingField { path, expected } => {
+                write!(f, r#"missing "{path}" field, expected {expected:?}"#)
+            }
+            Self::DataType {
+                path,
+                expected,
+                actual,
+            } => write!(f, r#""{path}" is {actual:?}, expected {expected:?}"#),
+            Self::UnexpectedField { path, actual } => {
+                write!(f, r#"unexpected "{path}" field of {actual:?}"#)
+            }
+            Self::Legacy => write!(f, r#"legacy "{DOUBLES}"/"{TRIPLES}" layout"#),
+        }
+    }
+}
+
+fn is_legacy(data_type: &DataType) -> bool {
+    let DataType::Struct(fields) = data_type else {
+        return false;
+    };
+    let names = fields.iter().map(Field::name).collect::<Vec<_>>();
+    !names.iter().any(|name| *name == "Unsaturated")
+        && names
+            .iter()
+            .any(|name| *name == DOUBLES || *name == TRIPLES)
+}
+
+fn compare(path: &str, expected: &DataType, actual: &DataType, mismatches: &mut Vec<Mismatch>) {
+    match (expected, actual) {
+        (DataType::Struct(expected), DataType::Struct(actual)) => {
+            let legacy = is_legacy(&DataType::Struct(actual.clone()));
+            for expected in expected {
+                let path = format!("{path}.{}", expected.name);
+                match actual.iter().find(|actual| actual.name == expected.name) {
+                    Some(actual) => compare(&path, &expected.dtype, &actual.dtype, mismatches),
+                    None if legacy && expected.name == "Unsaturated" => {}
+                    None => mismatches.push(Mismatch::MissingField {
+                        path,
+                        expected: expected.dtype.clone(),
+                    }),
+                }
+            }
+            for actual in actual {
+                if legacy && (actual.name == DOUBLES || actual.name == TRIPLES) {
+                    continue;
+                }
+                if !expected.iter().any(|expected| expected.name == actual.name) {
+                    mismatches.push(Mismatch::UnexpectedField {
+                        path: format!("{path}.{}", actual.name),
+                        actual: actual.dtype.clone(),
+                    });
+                }
+            }
+        }
+        (DataType::List(expected), DataType::List(actual)) => {
+            compare(&format!("{path}[]"), expected, actual, mismatches);
+        }
+        _ if expected != actual => mismatches.push(Mismatch::DataType {
+            path: path.to_owned(),
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+        _ => {}
+    }
+}
+
+fn field(name: &str, data_type: DataType) -> Expr {
+    col("")
+        .struct_()
+        .field_by_name(name)
+        .strict_cast(data_type)
+        .alias(name)
+}
+
+fn legacy(expr: Expr, name: &str, unsaturation: u8) -> Expr {
+    expr.struct_().field_by_name(name).list().eval(
+        as_struct(vec![
+            when(col("").eq(0))
+                .then(lit(NULL))
+                .otherwise(col("").abs())
+                .strict_cast(DataType::UInt8)
+                .alias("Index"),
+            when(col("").gt(0))
+                .then(lit(1))
+                .when(col("").lt(0))
+                .then(lit(-1))
+                .otherwise(lit(NULL))
+                .strict_cast(DataType::Int8)
+                .alias("Isomerism"),
+            lit(unsaturation)
+                .strict_cast(DataType::UInt8)
+                .alias("Unsaturation"),
+        ]),
+        true,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{fatty_acid, polars::DataFrameExt as _};
+
+    #[test]
+    fn valid() {
+        let data_frame = DataFrame::from_iter([fatty_acid!(18;9)]);
+        let report = validate_schema(&data_frame);
+        assert!(report.is_valid());
+        assert_eq!(report.coerce(data_frame.clone()).unwrap(), data_frame);
+    }
+
+    #[test]
+    fn missing() {
+        let report = validate_schema(&DataFrame::empty());
+        assert_eq!(report.mismatches, [Mismatch::MissingColumn]);
+        assert!(!report.is_coercible());
+        assert!(report.coerce(DataFrame::empty()).is_err());
+    }
+
+    #[test]
+    fn coerce() -> PolarsResult<()> {
+        let unsaturated = StructChunked::from_series(
+            PlSmallStr::EMPTY,
+            2,
+            [
+                Series::new("Index".into(), [9i32, 12]),
+                Series::new("Isomerism".into(), [1i32, -1]),
+                Series::new("Unsaturation".into(), [1i64, 1]),
+            ]
+            .iter(),
+        )?
+        .into_series();
+        let fatty_acid = StructChunked::from_series(
+            COLUMN.into(),
+            2,
+            [
+                Series::new("Unsaturated".into(), [Some(unsaturated), None]),
+                Series::new("Carbons".into(), [18i64, 16]),
+                Series::new("Comment".into(), ["", ""]),
+            ]
+            .iter(),
+        )?;
+        let data_frame = DataFrame::new(vec![fatty_acid.into_series().into()])?;
+        let report = validate_schema(&data_frame);
+        assert_eq!(report.mismatches.len(), 5);
+        assert!(report.mismatches.contains(&Mismatch::DataType {
+            path: "FattyAcid.Carbons".to_owned(),
+            expected: DataType::UInt8,
+            actual: DataType::Int64,
+        }));
+        assert!(report.mismatches.contains(&Mismatch::DataType {
+            path: "FattyAcid.Unsaturated[].Isomerism".to_owned(),
+            expected: DataType::Int8,
+            actual: DataType::Int32,
+        }));
+        assert!(report.mismatches.contains(&Mismatch::UnexpectedField {
+            path: "FattyAcid.Comment".to_owned(),
+            actual: DataType::String,
+        }));
+        assert!(report.is_coercible());
+        let coerced = report.coerce(data_frame)?;
+        assert!(validate_schema(&coerced).is_valid());
+        assert_eq!(coerced.schema().get(COLUMN), Some(&*DATA_TYPE));
+        let fatty_acid = coerced.fatty_acid();
+        assert_eq!(fatty_acid.get(0)?, Some(fatty_acid!(18;9,-12)));
+        assert_eq!(fatty_acid.get(1)?, Some(fatty_acid!(16)));
+        Ok(())
+    }
+
+    #[test]
+    fn legacy() -> PolarsResult<()> {
+        let doubles = Series::new(
+            DOUBLES.into(),
+            [
+                Some(Series::new(PlSmallStr::EMPTY, [9i8, -12])),
+                Some(Series::new(PlSmallStr::EMPTY, Vec::<i8>::new())),
+                None,
+            ],
+        );
+        let triples = Series::new(
+            TRIPLES.into(),
+            [
+                Some(Series::new(PlSmallStr::EMPTY, [15i8])),
+                Some(Series::new(PlSmallStr::EMPTY, Vec::<i8>::new())),
+                None,
+            ],
+        );
+        let carbons = Series::new("Carbons".into(), [18u8, 16, 17]);
+        let fatty_acid =
+            StructChunked::from_series(COLUMN.into(), 3, [carbons, doubles, triples].iter())?;
+        let data_frame = DataFrame::new(vec![fatty_acid.into_series().into()])?;
+        let report = validate_schema(&data_frame);
+        assert_eq!(report.mismatches, [Mismatch::Legacy]);
+        let coerced = report.coerce(data_frame)?;
+        assert_eq!(coerced.schema().get(COLUMN), Some(&*DATA_TYPE));
+        assert_eq!(
+            coerced.fatty_acid().to_vec()?,
+            [
+                Some(fatty_acid!(18;9,-12;15)),
+                Some(fatty_acid!(16)),
+                Some(fatty_acid!(17)),
+            ],
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn legacy_doubles() -> PolarsResult<()> {
+        let doubles = Series::new(
+            DOUBLES.into(),
+            [
+                Some(Series::new(PlSmallStr::EMPTY, [9i8, -12])),
+                Some(Series::new(PlSmallStr::EMPTY, Vec::<i8>::new())),
+            ],
+        );
+        let carbons = Series::new("Carbons".into(), [18u8, 16]);
+        let fatty_acid = StructChunked::from_series(COLUMN.into(), 2, [carbons, doubles].iter())?;
+        let data_frame = DataFrame::new(vec![fatty_acid.into_series().into()])?;
+        let report = validate_schema(&data_frame);
+        assert_eq!(report.mismatches, [Mismatch::Legacy]);
+        let coerced = report.coerce(data_frame)?;
+        assert_eq!(
+            coerced.fatty_acid().to_vec()?,
+            [Some(fatty_acid!(18;9,-12)), Some(fatty_acid!(16))],
+        );
+        Ok(())
+    }
+}
@@ -1,15 +1,27 @@
 use self::unsaturated::UnsaturatedSeries;
-use crate::fatty_acid::FattyAcid;
+use super::{COLUMN, DATA_TYPE};
+use crate::fatty_acid::{FattyAcid, sort};
 use polars::prelude::*;
 
 /// Extension methods for [`Series`]
 pub trait SeriesExt {
+    /// # Panics
+    ///
+    /// Panics if the series is not a fatty acid series, see
+    /// [`try_fatty_acid`](Self::try_fatty_acid).
     fn fatty_acid(&self) -> FattyAcidSeries;
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries>;
 }
 
 impl SeriesExt for Series {
     fn fatty_acid(&self) -> FattyAcidSeries {
-        FattyAcidSeries::new(self).expect(r#"Expected "FattyAcid" series"#)
+        self.try_fatty_acid()
+            .expect(r#"Expected "FattyAcid" series"#)
+    }
+
+    fn try_fatty_acid(&self) -> PolarsResult<FattyAcidSeries> {
+        FattyAcidSeries::new(self)
     }
 }
 
@@ -25,6 +37,8 @@ impl FattyAcidSeries {
         let r#struct = series.struct_()?;
         let carbons = r#struct.field_by_name("Carbons")?;
         let unsaturated = r#struct.field_by_name("Unsaturated")?;
+        carbons.u8()?;
+        unsaturated.list()?;
         Ok(Self {
             carbons,
             unsaturated,
@@ -46,19 +60,53 @@ impl FattyAcidSeries {
                 unsaturated.push(unsaturated_series.get(index)?);
             }
         };
-        unsaturated.sort_by_cached_key(|unsaturated| {
-            (
-                unsaturated.index,
-                unsaturated.isomerism,
-                unsaturated.unsaturation,
-            )
-        });
+        sort(&mut unsaturated);
         Ok(Some(FattyAcid {
             carbons,
             unsaturated,
         }))
     }
 
+    /// Iterate over fatty acids
+    ///
+    /// Walks the list offsets and the child arrays once instead of slicing
+    /// the list row by row like [`get`](Self::get).
+    pub fn iter(&self) -> PolarsResult<impl Iterator<Item = Option<FattyAcid>> + '_> {
+        let list = self
+            .unsaturated
+            .list()?
+            .rechunk_and_trim_to_normalized_offsets();
+        let offsets = list
+            .downcast_iter()
+            .next()
+            .expect("Expected rechunked list")
+            .offsets()
+            .clone();
+        let unsaturated = UnsaturatedSeries::new(&list.get_inner())?.to_vec()?;
+        Ok(self
+            .carbons
+            .u8()?
+            .iter()
+            .enumerate()
+            .map(move |(index, carbons)| {
+                let carbons = carbons?;
+                let (start, end) = offsets.start_end(index);
+                let mut unsaturated = unsaturated[start..end].to_vec();
+                sort(&mut unsaturated);
+                Some(FattyAcid {
+                    carbons,
+                    unsaturated,
+                })
+            }))
+    }
+
+    /// Collect all fatty acids
+    ///
+    /// See [`iter`](Self::iter).
+    pub fn to_vec(&self) -> PolarsResult<Vec<Option<FattyAcid>>> {
+        Ok(self.iter()?.collect())
+    }
+
     pub fn unsaturated(&self, index: usize) -> PolarsResult<Option<UnsaturatedSeries>> {
         let Some(unsaturated) = self.unsaturated.list()?.get_as_series(index) else {
             return Ok(None);
@@ -67,4 +115,109 @@ impl FattyAcidSeries {
     }
 }
 
+impl FromIterator<FattyAcid> for Series {
+    fn from_iter<T: IntoIterator<Item = FattyAcid>>(iter: T) -> Self {
+        Series::new(COLUMN.into(), iter.into_iter().collect::<Vec<_>>())
+    }
+}
+
+impl NamedFrom<&[FattyAcid], FattyAcid> for Series {
+    fn new(name: PlSmallStr, fatty_acids: &[FattyAcid]) -> Self {
+        let values = fatty_acids.iter().map(AnyValue::from).collect::<Vec<_>>();
+        from_any_values(name, &values)
+    }
+}
+
+impl NamedFrom<&[Option<FattyAcid>], FattyAcid> for Series {
+    fn new(name: PlSmallStr, fatty_acids: &[Option<FattyAcid>]) -> Self {
+        let values = fatty_acids
+            .iter()
+            .map(|fatty_acid| fatty_acid.as_ref().map_or(AnyValue::Null, AnyValue::from))
+            .collect::<Vec<_>>();
+        from_any_values(name, &values)
+    }
+}
+
+impl NamedFrom<Vec<FattyAcid>, FattyAcid> for Series {
+    fn new(name: PlSmallStr, fatty_acids: Vec<FattyAcid>) -> Self {
+        Series::new(name, fatty_acids.as_slice())
+    }
+}
+
+impl NamedFrom<Vec<Option<FattyAcid>>, FattyAcid> for Series {
+    fn new(name: PlSmallStr, fatty_acids: Vec<Option<FattyAcid>>) -> Self {
+        Series::new(name, fatty_acids.as_slice())
+    }
+}
+
+fn from_any_values(name: PlSmallStr, values: &[AnyValue]) -> Series {
+    Series::from_any_values_and_dtype(name, values, &DATA_TYPE, true)
+        .expect(r#"Expected "FattyAcid" values"#)
+}
+
 pub mod unsaturated;
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::fatty_acid;
+
+    #[test]
+    fn round_trip() -> PolarsResult<()> {
+        let fatty_acids = vec![
+            Some(fatty_acid!(16)),
+            None,
+            Some(fatty_acid!(18;9)),
+            Some(fatty_acid!(18;9,12)),
+            Some(fatty_acid!(18;-9,12;15)),
+            None,
+        ];
+        let series = Series::new(COLUMN.into(), fatty_acids.clone());
+        assert_eq!(series.dtype(), &*DATA_TYPE);
+        assert_eq!(series.null_count(), 2);
+        let decoded = series.fatty_acid().to_vec()?;
+        assert_eq!(decoded, fatty_acids);
+        let encoded = Series::new(COLUMN.into(), decoded);
+        assert!(encoded.equals_missing(&series));
+        let data_frame = fatty_acids.into_iter().flatten().collect::<DataFrame>();
+        assert!(
+            data_frame[COLUMN]
+                .as_materialized_series()
+                .equals_missing(&series.drop_nulls())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn empty() -> PolarsResult<()> {
+        let series = Series::new(COLUMN.into(), Vec::<FattyAcid>::new());
+        assert_eq!(series.dtype(), &*DATA_TYPE);
+        assert!(series.is_empty());
+        assert!(series.fatty_acid().to_vec()?.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn iter() -> PolarsResult<()> {
+        let series = Series::new(
+            COLUMN.into(),
+            vec![
+                Some(fatty_acid!(18;12,9)),
+                None,
+                Some(fatty_acid!(20;5,8,11,14,17)),
+                Some(fatty_acid!(22;4,7,10,13,16,19)),
+            ],
+        );
+        // Sliced and multi-chunk series
+        let mut appended = series.slice(1, 3);
+        appended.append(&series)?;
+        let fatty_acid = appended.fatty_acid();
+        let expected = (0..fatty_acid.len())
+            .map(|index| fatty_acid.get(index))
+            .collect::<PolarsResult<Vec<_>>>()?;
+        assert_eq!(fatty_acid.to_vec()?, expected);
+        assert_eq!(expected[0], None);
+        assert_eq!(expected[3], Some(fatty_acid!(18;9,12)));
+        Ok(())
+    }
+}
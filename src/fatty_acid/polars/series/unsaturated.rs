@@ -41,4 +41,24 @@ impl UnsaturatedSeries {
                 .and_then(|unsaturation| unsaturation.try_into().ok()),
         })
     }
+
+    /// Iterate over unsaturated bonds
+    pub fn iter(&self) -> PolarsResult<impl Iterator<Item = Unsaturated> + '_> {
+        Ok(self
+            .index
+            .u8()?
+            .iter()
+            .zip(self.isomerism.i8()?)
+            .zip(self.unsaturation.u8()?)
+            .map(|((index, isomerism), unsaturation)| Unsaturated {
+                index,
+                isomerism: isomerism.and_then(|isomerism| isomerism.try_into().ok()),
+                unsaturation: unsaturation.and_then(|unsaturation| unsaturation.try_into().ok()),
+            }))
+    }
+
+    /// Collect all unsaturated bonds
+    pub fn to_vec(&self) -> PolarsResult<Vec<Unsaturated>> {
+        Ok(self.iter()?.collect())
+    }
 }
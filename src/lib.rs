@@ -2,3 +2,5 @@
 
 pub mod r#const;
 pub mod fatty_acid;
+#[cfg(feature = "reference")]
+pub mod reference;
@@ -0,0 +1,73 @@
+use super::Provenance;
+use crate::fatty_acid::polars::validate_schema;
+use polars::prelude::*;
+use std::sync::LazyLock;
+
+/// Mature human milk provenance
+pub const HUMAN_MILK: Provenance = Provenance {
+    name: "Mature human milk",
+    description: "Fatty acid composition of mature human milk fat: `Median`, \
+                  `InterquartileRange` and `ReferenceRange` (`Min`, `Max`) of \
+                  the triacylglycerols (`StereospecificNumber123`) and of their \
+                  sn-2 position (`StereospecificNumber2`)",
+    file: "MatureMilk.ron",
+    rows: 33,
+    source: Some(
+        "`special/mature_milk/MatureMilk.ron` of the original tree, the publication is not recorded",
+    ),
+};
+
+static MATURE_MILK: LazyLock<DataFrame> = LazyLock::new(|| {
+    let data_frame =
+        ron::de::from_str(include_str!("MatureMilk.ron")).expect("deserialize MatureMilk.ron");
+    let report = validate_schema(&data_frame);
+    assert!(report.is_valid(), "MatureMilk.ron schema: {report}");
+    assert_eq!(data_frame.height(), HUMAN_MILK.rows, "MatureMilk.ron rows");
+    data_frame
+});
+
+/// Mature human milk reference, see [`HUMAN_MILK`]
+///
+/// The fatty acid column conforms to [`SCHEMA`](crate::fatty_acid::polars::SCHEMA).
+pub fn human_milk() -> DataFrame {
+    MATURE_MILK.clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        fatty_acid,
+        polars::{COLUMN, DataFrameExt as _},
+    };
+
+    #[test]
+    fn rows() {
+        assert_eq!(super::human_milk().height(), HUMAN_MILK.rows);
+    }
+
+    #[test]
+    fn human_milk() -> PolarsResult<()> {
+        let data_frame = super::human_milk();
+        assert!(validate_schema(&data_frame).is_valid());
+        assert_eq!(
+            data_frame.get_column_names_str(),
+            [COLUMN, "StereospecificNumber123", "StereospecificNumber2"],
+        );
+        let fatty_acids = data_frame.fatty_acid().to_vec()?;
+        assert!(fatty_acids.contains(&Some(fatty_acid!(16))));
+        assert!(fatty_acids.contains(&Some(fatty_acid!(18;9))));
+        let range = data_frame["StereospecificNumber123"]
+            .struct_()?
+            .field_by_name("ReferenceRange")?;
+        let range = range.struct_()?;
+        let min = range.field_by_name("Min")?;
+        let max = range.field_by_name("Max")?;
+        for (min, max) in min.f64()?.iter().zip(max.f64()?) {
+            if let (Some(min), Some(max)) = (min, max) {
+                assert!(min <= max);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,20 @@
+pub use self::{
+    human_milk::{HUMAN_MILK, human_milk},
+    oils::{REFERENCE_RANGE, VALUE, oil, oils, standards},
+};
+
+/// Reference dataset provenance
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Provenance {
+    pub name: &'static str,
+    pub description: &'static str,
+    /// Bundled file
+    pub file: &'static str,
+    /// Number of fatty acids
+    pub rows: usize,
+    /// Publication, laboratory or file the data come from, if known
+    pub source: Option<&'static str>,
+}
+
+mod human_milk;
+mod oils;
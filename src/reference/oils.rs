@@ -0,0 +1,132 @@
+use super::Provenance;
+use crate::fatty_acid::polars::{COLUMN, Standard, Standards};
+use polars::prelude::*;
+use std::sync::LazyLock;
+
+/// Edible oil and fat reference value column name, the middle of the range
+pub const VALUE: &str = "Value";
+/// Edible oil and fat reference range column name, a `Min`, `Max` struct
+pub const REFERENCE_RANGE: &str = "ReferenceRange";
+
+const FILE: &str = "Oils.ron";
+
+static OILS: LazyLock<Standards> =
+    LazyLock::new(|| Standards::from_ron(include_str!("Oils.ron")).expect("deserialize Oils.ron"));
+
+/// Edible oil and fat standards, e.g. for
+/// [`Standard::check`](crate::fatty_acid::polars::Standard::check)
+pub fn standards() -> &'static Standards {
+    &OILS
+}
+
+/// Edible oil and fat references
+///
+/// Every reference is the fatty acid composition table of a Codex Alimentarius
+/// standard, see [`Provenance::source`]. Cocoa butter (CXS 86) and milk fat
+/// (CXS 280) are not included, their standards set no fatty acid ranges.
+pub fn oils() -> impl Iterator<Item = Provenance> {
+    OILS.standards.iter().map(provenance)
+}
+
+/// Edible oil or fat reference by name, case insensitive, see [`oils`]
+///
+/// Returns the fatty acid [`COLUMN`], the [`VALUE`] and the
+/// [`REFERENCE_RANGE`] columns, % of total fatty acids. Fatty acids without a
+/// position, e.g. `18:1`, are the sum of all positional isomers; a not
+/// detected minimum is 0.
+pub fn oil(name: &str) -> Option<DataFrame> {
+    Some(data_frame(OILS.get(name)?))
+}
+
+fn provenance(standard: &'static Standard) -> Provenance {
+    Provenance {
+        name: &standard.name,
+        description: &standard.description,
+        file: FILE,
+        rows: standard.fatty_acids.len(),
+        source: standard.source.as_deref(),
+    }
+}
+
+fn data_frame(standard: &Standard) -> DataFrame {
+    let limits = &standard.fatty_acids;
+    let min = limits.iter().map(|limits| limits.min);
+    let max = limits.iter().map(|limits| limits.max);
+    let value = min
+        .clone()
+        .zip(max.clone())
+        .map(|(min, max)| (min + max) / 2.0);
+    let range = StructChunked::from_series(
+        REFERENCE_RANGE.into(),
+        limits.len(),
+        [
+            Series::from_iter(min).with_name("Min".into()),
+            Series::from_iter(max).with_name("Max".into()),
+        ]
+        .iter(),
+    )
+    .expect("reference range");
+    DataFrame::new(vec![
+        Series::new(
+            COLUMN.into(),
+            limits
+                .iter()
+                .map(|limits| limits.fatty_acid.clone())
+                .collect::<Vec<_>>(),
+        )
+        .into(),
+        Series::from_iter(value).with_name(VALUE.into()).into(),
+        range.into_series().into(),
+    ])
+    .expect("reference data frame")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::fatty_acid::{
+        fatty_acid,
+        polars::{DataFrameExt as _, validate_schema},
+    };
+
+    #[test]
+    fn oils() -> PolarsResult<()> {
+        assert!(super::oils().all(|oil| oil.source.is_some()));
+        let names = super::oils().map(|oil| oil.name).collect::<Vec<_>>();
+        assert_eq!(names.len(), 10);
+        for name in names {
+            let data_frame = super::oil(name).unwrap();
+            assert!(validate_schema(&data_frame).is_valid(), "{name}");
+            let range = data_frame[REFERENCE_RANGE].struct_()?;
+            let min = range.field_by_name("Min")?;
+            let max = range.field_by_name("Max")?;
+            for (min, max) in min.f64()?.iter().zip(max.f64()?) {
+                assert!(min.unwrap() <= max.unwrap(), "{name}");
+            }
+            // Minima of a composition can not exceed 100 %
+            assert!(min.f64()?.sum().unwrap() <= 100.0, "{name}");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn oil() -> PolarsResult<()> {
+        let coconut = super::oil("coconut").unwrap();
+        assert_eq!(coconut.fatty_acid().get(3)?, Some(fatty_acid!(12)),);
+        assert!((coconut[VALUE].f64()?.get(3).unwrap() - 49.15).abs() < 1e-9);
+        let anchovy = super::oil("Anchovy").unwrap();
+        assert!(
+            anchovy
+                .fatty_acid()
+                .to_vec()?
+                .contains(&Some(fatty_acid!(22;4,7,10,13,16,19)))
+        );
+        assert!(super::oil("Olive").is_some());
+        assert!(super::oil("Unknown").is_none());
+        // The composition in the middle of the ranges conforms
+        let soybean = super::oil("Soybean").unwrap();
+        let compliance = standards().get("soybean").unwrap().check(&soybean, VALUE)?;
+        assert!(compliance.passed());
+        Ok(())
+    }
+}